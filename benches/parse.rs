@@ -0,0 +1,94 @@
+//! Compares the helpers in `advent_of_code::parse` with `str::parse`.
+//!
+//! Run with `cargo bench --bench parse`. Without `--bench`, e.g. in `cargo test --benches`, every
+//! benchmark runs once to check that it works.
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use advent_of_code::parse::{int, ints, pair};
+
+/// Time each benchmark is run for.
+const BENCH_TIME: Duration = Duration::from_secs(1);
+
+fn bench<T>(name: &str, is_bench: bool, func: impl Fn() -> T) {
+    if !is_bench {
+        black_box(func());
+        return;
+    }
+
+    let mut samples = 0u32;
+    let timer = Instant::now();
+    while timer.elapsed() < BENCH_TIME {
+        black_box(func());
+        samples += 1;
+    }
+
+    println!(
+        "{name:<32} {:>12.1?} ({samples} samples)",
+        timer.elapsed() / samples
+    );
+}
+
+/// A puzzle-like input of ranges, one per line, with numbers of varying length.
+fn generate_input(lines: u64) -> String {
+    (0..lines)
+        .map(|i| {
+            let start = i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (i % 48 + 16);
+            format!("{start}-{}\n", start + i)
+        })
+        .collect()
+}
+
+fn main() {
+    let is_bench = env::args().any(|arg| arg == "--bench");
+    let input = generate_input(10_000);
+
+    let lines: Vec<&str> = input.lines().collect();
+    let numbers: Vec<&str> = lines.iter().flat_map(|line| line.split('-')).collect();
+
+    bench("str::parse", is_bench, || {
+        numbers
+            .iter()
+            .map(|s| s.parse::<u64>().unwrap())
+            .fold(0, |acc, x| acc ^ x)
+    });
+
+    bench("parse::int", is_bench, || {
+        numbers
+            .iter()
+            .map(|s| int::<u64>(s).unwrap())
+            .fold(0, |acc, x| acc ^ x)
+    });
+
+    bench("split_once + str::parse", is_bench, || {
+        lines
+            .iter()
+            .map(|line| {
+                let (a, b) = line.split_once('-').unwrap();
+                (a.parse::<u64>().unwrap(), b.parse::<u64>().unwrap())
+            })
+            .fold(0, |acc, (a, b)| acc ^ a ^ b)
+    });
+
+    bench("parse::pair", is_bench, || {
+        lines
+            .iter()
+            .map(|line| pair::<u64>(line, "-").unwrap())
+            .fold(0, |acc, (a, b)| acc ^ a ^ b)
+    });
+
+    bench("split + str::parse", is_bench, || {
+        input
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<u64>().unwrap())
+            .fold(0, |acc, x| acc ^ x)
+    });
+
+    bench("parse::ints", is_bench, || {
+        ints::<u64>(&input).fold(0, |acc, x| acc ^ x)
+    });
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../src/bin/06.rs"]
+mod solution;
+
+fuzz_target!(|input: &str| {
+    let _ = solution::parse_problems(input);
+    let _ = solution::parse_worksheet(input);
+});
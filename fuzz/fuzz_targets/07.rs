@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../src/bin/07.rs"]
+mod solution;
+
+fuzz_target!(|input: &str| {
+    let _ = solution::parse_manifold(input);
+});
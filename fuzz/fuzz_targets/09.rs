@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../src/bin/09.rs"]
+mod solution;
+
+fuzz_target!(|input: &str| {
+    let _ = solution::parse_tiles::<i64>(input);
+    let _ = solution::parse_tiles::<usize>(input);
+});
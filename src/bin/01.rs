@@ -1,5 +1,19 @@
+use advent_of_code::parse::int;
+
 advent_of_code::solution!(1);
 
+/// Parses rotations like `L68` into their direction and distance.
+pub fn parse_rotations(input: &str) -> Option<Vec<(u8, isize)>> {
+    input
+        .split_whitespace()
+        .map(|rotation| {
+            let (&dir, number) = rotation.as_bytes().split_first()?;
+            let number = int::<u32>(number)?;
+            matches!(dir, b'L' | b'R').then_some((dir, number as isize))
+        })
+        .collect()
+}
+
 fn range(value: isize, range: isize) -> isize {
     ((value % range) + range) % range
 }
@@ -7,14 +21,10 @@ fn range(value: isize, range: isize) -> isize {
 pub fn part_one(input: &str) -> Option<u64> {
     let mut password = 0;
     let mut dial: isize = 50;
-    let rotations = input.split_whitespace();
-    for rotation in rotations {
-        let (dir, number) = rotation.split_at(1); // We know that wvery rotation string it's formatted: {R|L}{number}
-        let number = number.parse::<isize>().ok()?; // u8 it's fine because of the 0..99 range
+    for (dir, number) in parse_rotations(input)? {
         match dir {
-            "L" => dial = range(dial + number, 100),
-            "R" => dial = range(dial - number, 100),
-            _ => unreachable!(),
+            b'L' => dial = range(dial + number, 100),
+            _ => dial = range(dial - number, 100),
         };
         if dial == 0 {
             password += 1;
@@ -26,24 +36,20 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let mut password = 0;
     let mut dial: isize = 50;
-    let rotations = input.split_whitespace();
-    for rotation in rotations {
-        let (dir, number) = rotation.split_at(1); // We know that wvery rotation string it's formatted: {R|L}{number}
-        let number = number.parse::<isize>().ok()?; // u8 it's fine because of the 0..99 range
+    for (dir, number) in parse_rotations(input)? {
         match dir {
-            "L" => {
+            b'L' => {
                 if number >= 100 - dial {
                     password += 1 + ((number - (100 - dial)) / 100) as u64;
                 }
                 dial = range(dial + number, 100);
             }
-            "R" => {
+            _ => {
                 if number > dial {
                     password += 1 + ((number - dial - 1) / 100) as u64;
                 }
                 dial = range((dial - number) % 100, 100);
             }
-            _ => unreachable!(),
         };
     }
     Some(password)
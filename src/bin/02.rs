@@ -1,11 +1,18 @@
+use advent_of_code::{
+    math::{POWERS_OF_TEN, digit_count, divisors},
+    parse::pair,
+};
+
 advent_of_code::solution!(2);
 
+/// Parses the comma-separated ID ranges like `11-22`.
+pub fn parse_ranges(input: &str) -> Option<Vec<(u64, u64)>> {
+    input.split(',').map(|range| pair(range, "-")).collect()
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let inputs = input.split(',');
     let mut seq = 0;
-    for input in inputs {
-        let (start, end) = input.split_once('-')?;
-        let (start, end) = (start.parse::<u64>().ok()?, end.parse::<u64>().ok()?);
+    for (start, end) in parse_ranges(input)? {
         for number in start..=end {
             if invalid_number(number) {
                 seq += number;
@@ -16,36 +23,18 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn invalid_number(number: u64) -> bool {
-    let length = number.ilog10() + 1;
+    let length = digit_count(number);
     if !length.is_multiple_of(2) {
         return false;
     }
-    let length = 10u64.pow(length / 2) + 1;
-    number.is_multiple_of(length)
+    number.is_multiple_of(POWERS_OF_TEN[length as usize / 2] + 1)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    const POWERS: [u64; 20] = {
-        let mut p = [0u64; 20];
-        let mut i = 0;
-        while i != 20 {
-            p[i] = 10u64.pow(i as u32);
-            i += 1;
-        }
-        p
-    };
-    let inputs = input.split(',');
     let mut seq = 0;
-    for input in inputs {
-        let (start, end) = input.split_once('-')?;
-        let (start, end) = (start.parse::<u64>().ok()?, end.parse::<u64>().ok()?);
-
-        let mut num_digits = POWERS.iter().position(|x| start < *x).unwrap_or(20);
-        let mut next_boundary = if num_digits < 20 {
-            POWERS[num_digits]
-        } else {
-            u64::MAX
-        };
+    for (start, end) in parse_ranges(input)? {
+        let mut num_digits = digit_count(start);
+        let mut next_boundary = next_power_of_ten(num_digits);
 
         let mut divisors = compute_divisors(num_digits);
 
@@ -53,11 +42,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         while number <= end {
             if number >= next_boundary {
                 num_digits += 1;
-                next_boundary = if num_digits < 20 {
-                    POWERS[num_digits]
-                } else {
-                    u64::MAX
-                };
+                next_boundary = next_power_of_ten(num_digits);
                 divisors = compute_divisors(num_digits);
             }
 
@@ -71,27 +56,25 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(seq)
 }
 
-fn compute_divisors(num_digits: usize) -> Vec<u64> {
-    let mut divisors = Vec::with_capacity(num_digits);
-
-    for segment_len in 1..=(num_digits / 2) {
-        if !num_digits.is_multiple_of(segment_len) {
-            continue;
-        }
-
-        let num_repetitions = num_digits / segment_len;
-        let mut divisor = 0u64;
-        let mut power = 1u64;
-
-        for _ in 0..num_repetitions {
-            divisor += power;
-            power *= 10u64.pow(segment_len as u32);
-        }
-
-        divisors.push(divisor);
-    }
+/// The smallest number with more than `num_digits` digits, or `u64::MAX` if there is none.
+fn next_power_of_ten(num_digits: u32) -> u64 {
+    POWERS_OF_TEN
+        .get(num_digits as usize)
+        .copied()
+        .unwrap_or(u64::MAX)
+}
 
-    divisors
+/// The numbers that a number of `num_digits` digits is a multiple of if it repeats a segment,
+/// e.g. `10101` for a segment of two digits repeated three times.
+fn compute_divisors(num_digits: u32) -> Vec<u64> {
+    divisors(u64::from(num_digits))
+        .into_iter()
+        .filter(|&segment_len| segment_len < u64::from(num_digits))
+        .map(|segment_len| {
+            let shift = POWERS_OF_TEN[segment_len as usize];
+            (1..num_digits / segment_len as u32).fold(1, |divisor, _| divisor * shift + 1)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -109,4 +92,35 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4_174_379_265));
     }
+
+    /// Naive version of part two that checks if the digits are a repeated sequence.
+    #[cfg(feature = "testing")]
+    fn part_two_naive(input: &str) -> Option<u64> {
+        let mut seq = 0;
+        for range in input.split(',') {
+            let (start, end) = range.split_once('-')?;
+            for number in start.parse::<u64>().ok()?..=end.parse::<u64>().ok()? {
+                let digits = number.to_string();
+                let is_repeated = (1..=digits.len() / 2).any(|len| {
+                    digits.len().is_multiple_of(len)
+                        && digits == digits[..len].repeat(digits.len() / len)
+                });
+                if is_repeated {
+                    seq += number;
+                }
+            }
+        }
+        Some(seq)
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_part_two_matches_naive() {
+        use advent_of_code::testing;
+
+        advent_of_code::differential!(part_two, part_two_naive, |rng| {
+            let max = 10u64.pow(rng.range(1..=4) as u32);
+            testing::range_list(rng, 3, 1..=max, ",")
+        });
+    }
 }
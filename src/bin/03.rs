@@ -1,15 +1,19 @@
 advent_of_code::solution!(3);
 
-fn parse_file(input: &str) -> Vec<&str> {
-    input.split('\n').collect::<Vec<_>>()
+/// Parses the banks of batteries, one per line, into their joltage digits.
+pub fn parse_banks(input: &str) -> Option<Vec<Vec<u8>>> {
+    input
+        .lines()
+        .map(|line| {
+            let is_valid = !line.is_empty() && line.bytes().all(|b| b.is_ascii_digit());
+            is_valid.then(|| line.bytes().map(|b| b - b'0').collect())
+        })
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let lines = parse_file(input)
-        .into_iter()
-        .map(|x| x.bytes().map(|b| b - b'0').collect::<Vec<_>>());
     let mut sum = 0usize;
-    for line in lines {
+    for line in parse_banks(input)? {
         let n = line.len();
         let mut best_right = vec![0; n];
         best_right[n - 1] = 0;
@@ -30,11 +34,8 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let lines = parse_file(input)
-        .into_iter()
-        .map(|x| x.bytes().map(|b| b - b'0').collect::<Vec<_>>());
     let mut sum = 0usize;
-    for line in lines {
+    for line in parse_banks(input)? {
         let k = 12;
         let mut to_remove = line.len().saturating_sub(k);
         let mut stack: Vec<u8> = Vec::with_capacity(line.len());
@@ -76,4 +77,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(3_121_910_778_619));
     }
+
+    #[test]
+    fn test_trailing_line_break() {
+        assert_eq!(part_one("12\n34\n"), Some(46));
+        assert_eq!(part_two("12\n34\n"), Some(46));
+    }
 }
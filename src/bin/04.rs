@@ -1,142 +1,48 @@
-advent_of_code::solution!(4);
-
-pub fn part_one(input: &str) -> Option<u64> {
-    let lines: Vec<&str> = input.lines().collect();
-    let rows = lines.len();
-    let cols = lines[0].len();
+use advent_of_code::grid::{Grid, Pos};
 
-    // Use flat byte array for better cache performance
-    let mut grid: Vec<u8> = lines.iter().flat_map(|l| l.bytes()).collect();
+advent_of_code::solution!(4);
 
-    let mut sum = 0;
+const ROLL: u8 = b'@';
 
-    for r in 0..rows {
-        let row_start = r * cols;
-        for c in 0..cols {
-            let idx = row_start + c;
-            if grid[idx] == b'@' {
-                let mut count = 0;
+/// Parses the grid of paper rolls, which has to be rectangular.
+pub fn parse_grid(input: &str) -> Option<Grid<u8>> {
+    let width = input.lines().next().map_or(0, str::len);
+    let is_rectangular = input.lines().all(|line| line.len() == width);
+    is_rectangular.then(|| Grid::from_bytes(input))
+}
 
-                // Check all 8 neighbors
-                if r > 0 {
-                    let prev_row = (r - 1) * cols;
-                    if c > 0 && (grid[prev_row + c - 1] == b'@' || grid[prev_row + c - 1] == b'x') {
-                        count += 1;
-                    }
-                    if grid[prev_row + c] == b'@' || grid[prev_row + c] == b'x' {
-                        count += 1;
-                    }
-                    if c + 1 < cols
-                        && (grid[prev_row + c + 1] == b'@' || grid[prev_row + c + 1] == b'x')
-                    {
-                        count += 1;
-                    }
-                }
-                if count < 4 && c > 0 && (grid[idx - 1] == b'@' || grid[idx - 1] == b'x') {
-                    count += 1;
-                }
-                if count < 4 && c + 1 < cols && (grid[idx + 1] == b'@' || grid[idx + 1] == b'x') {
-                    count += 1;
-                }
-                if count < 4 && r + 1 < rows {
-                    let next_row = (r + 1) * cols;
-                    if c > 0 && (grid[next_row + c - 1] == b'@' || grid[next_row + c - 1] == b'x') {
-                        count += 1;
-                    }
-                    if count < 4 && (grid[next_row + c] == b'@' || grid[next_row + c] == b'x') {
-                        count += 1;
-                    }
-                    if count < 4
-                        && c + 1 < cols
-                        && (grid[next_row + c + 1] == b'@' || grid[next_row + c + 1] == b'x')
-                    {
-                        count += 1;
-                    }
-                }
+/// A roll can be reached by a forklift if fewer than four of its neighbours are rolls.
+fn is_accessible(grid: &Grid<u8>, pos: Pos) -> bool {
+    grid[pos] == ROLL && grid.neighbours_8(pos).filter(|&n| grid[n] == ROLL).count() < 4
+}
 
-                if count < 4 {
-                    grid[idx] = b'x';
-                    sum += 1;
-                }
-            }
-        }
-    }
-    Some(sum)
+pub fn part_one(input: &str) -> Option<u64> {
+    let grid = parse_grid(input)?;
+    let accessible = grid
+        .positions()
+        .filter(|&pos| is_accessible(&grid, pos))
+        .count();
+    Some(accessible as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let lines: Vec<&str> = input.lines().collect();
-    let rows = lines.len();
-    let cols = lines[0].len();
-
-    // Use flat byte array for better cache performance
-    let mut grid: Vec<u8> = lines.iter().flat_map(|l| l.bytes()).collect();
-
+    let mut grid = parse_grid(input)?;
     let mut sum = 0;
-    let mut positions_to_remove = Vec::with_capacity(128);
 
     loop {
-        positions_to_remove.clear();
-
-        // Find all positions to remove in this iteration
-        for r in 0..rows {
-            let row_start = r * cols;
-            for c in 0..cols {
-                let idx = row_start + c;
-                if grid[idx] == b'@' {
-                    let mut count = 0;
-
-                    // Manually unrolled neighbor checks for performance
-                    // Top row
-                    if r > 0 {
-                        let prev_row = (r - 1) * cols;
-                        if c > 0 && grid[prev_row + c - 1] == b'@' {
-                            count += 1;
-                        }
-                        if grid[prev_row + c] == b'@' {
-                            count += 1;
-                        }
-                        if c + 1 < cols && grid[prev_row + c + 1] == b'@' {
-                            count += 1;
-                        }
-                    }
-                    // Middle row (left and right)
-                    if count < 4 && c > 0 && grid[idx - 1] == b'@' {
-                        count += 1;
-                    }
-                    if count < 4 && c + 1 < cols && grid[idx + 1] == b'@' {
-                        count += 1;
-                    }
-                    // Bottom row
-                    if count < 4 && r + 1 < rows {
-                        let next_row = (r + 1) * cols;
-                        if c > 0 && grid[next_row + c - 1] == b'@' {
-                            count += 1;
-                        }
-                        if count < 4 && grid[next_row + c] == b'@' {
-                            count += 1;
-                        }
-                        if count < 4 && c + 1 < cols && grid[next_row + c + 1] == b'@' {
-                            count += 1;
-                        }
-                    }
-
-                    if count < 4 {
-                        positions_to_remove.push(idx);
-                    }
-                }
-            }
-        }
+        let removable: Vec<Pos> = grid
+            .positions()
+            .filter(|&pos| is_accessible(&grid, pos))
+            .collect();
 
-        if positions_to_remove.is_empty() {
+        if removable.is_empty() {
             break;
         }
 
-        sum += positions_to_remove.len() as u64;
+        sum += removable.len() as u64;
 
-        // Remove all marked positions
-        for &idx in &positions_to_remove {
-            grid[idx] = b'.';
+        for pos in removable {
+            grid[pos] = b'.';
         }
     }
 
@@ -1,112 +1,36 @@
-advent_of_code::solution!(5);
+use advent_of_code::{
+    interval_set::IntervalSet,
+    parse::{int, pair, sections},
+};
 
-const SPECIAL_WHITESPACE: &str = "\r\n\r\n";
+advent_of_code::solution!(5);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (ranges_str, numbers_str) = input.split_once(SPECIAL_WHITESPACE)?;
+/// Parses the fresh ingredient ID ranges, merging the ones that overlap, and the available
+/// ingredient IDs.
+pub fn parse_database(input: &str) -> Option<(IntervalSet<u64>, Vec<u64>)> {
+    let mut sections = sections(input);
+    let (ranges_str, ids_str) = (sections.next()?, sections.next().unwrap_or_default());
 
-    let mut ranges = ranges_str
+    let fresh = ranges_str
         .lines()
-        .filter_map(|line| {
-            let (start, end) = line.split_once('-')?;
-            let start = start.parse::<u64>().ok()?;
-            let end = end.parse::<u64>().ok()?;
-            Some((start, end))
-        })
-        .collect::<Vec<_>>();
-
-    ranges.sort_unstable_by_key(|&(start, _)| start);
-    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        .map(|line| pair::<u64>(line, "-").map(|(start, end)| start..=end))
+        .collect::<Option<_>>()?;
+    let ids = ids_str.lines().map(int::<u64>).collect::<Option<_>>()?;
 
-    // Here we are merging overlapping ranges to optimize the search later
-    // For example, if we have ranges (1-5), (4-10), (12-15)
-    // We will merge (1-5) and (4-10) into (1-10)
-    // But how?
-    // We iterate over the sorted ranges and for each range we check if it overlaps with the last range in the merged list
-    // If it does, we merge them by updating the end of the last range in the merged list
-    // If it doesn't, we simply add the new range to the merged list
-    // This way we ensure that we have the minimum number of ranges to check against later
-    for (start, end) in ranges {
-        // "if we have at least one range merged already and the start of the current range is less than or equal to the end of the last merged range plus one (to account for contiguous ranges)"
-        // then we merge the ranges
-        if let Some(range) = merged.last_mut()
-            && start <= range.1 + 1
-        {
-            // We update the end of the last merged range to be the maximum of the current end and the last merged end
-            // So, for example if we have (1-5) and (4-10) being `start = 4` and `end = 10` and `range = (1, 5)`
-            // We will update `range.1` to be `10`, resulting in `range = (1, 10)`
-            range.1 = range.1.max(end);
-            continue;
-        }
-        merged.push((start, end));
-    }
+    Some((fresh, ids))
+}
 
-    let count = numbers_str
-        .lines()
-        .filter_map(|line| line.parse::<u64>().ok())
-        .filter(|&num| {
-            merged
-                .binary_search_by(|&(start, end)| {
-                    if num < start {
-                        std::cmp::Ordering::Greater
-                    } else if num > end {
-                        std::cmp::Ordering::Less
-                    } else {
-                        std::cmp::Ordering::Equal
-                    }
-                })
-                .is_ok()
-        })
-        .count();
+pub fn part_one(input: &str) -> Option<u64> {
+    let (fresh, ids) = parse_database(input)?;
+    let count = ids.iter().filter(|&&id| fresh.contains(id)).count();
 
     Some(count as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (ranges_str, ..) = input.split_once(SPECIAL_WHITESPACE)?;
-
-    let mut ranges = ranges_str
-        .lines()
-        .filter_map(|line| {
-            let (start, end) = line.split_once('-')?;
-            let start = start.parse::<u64>().ok()?;
-            let end = end.parse::<u64>().ok()?;
-            Some((start, end))
-        })
-        .collect::<Vec<_>>();
-
-    ranges.sort_unstable_by_key(|&(start, _)| start);
-    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
-
-    // Here we are merging overlapping ranges to optimize the search later
-    // For example, if we have ranges (1-5), (4-10), (12-15)
-    // We will merge (1-5) and (4-10) into (1-10)
-    // But how?
-    // We iterate over the sorted ranges and for each range we check if it overlaps with the last range in the merged list
-    // If it does, we merge them by updating the end of the last range in the merged list
-    // If it doesn't, we simply add the new range to the merged list
-    // This way we ensure that we have the minimum number of ranges to check against later
-    for (start, end) in ranges {
-        // "if we have at least one range merged already and the start of the current range is less than or equal to the end of the last merged range plus one (to account for contiguous ranges)"
-        // then we merge the ranges
-        if let Some(range) = merged.last_mut()
-            && start <= range.1 + 1
-        {
-            // We update the end of the last merged range to be the maximum of the current end and the last merged end
-            // So, for example if we have (1-5) and (4-10) being `start = 4` and `end = 10` and `range = (1, 5)`
-            // We will update `range.1` to be `10`, resulting in `range = (1, 10)`
-            range.1 = range.1.max(end);
-            continue;
-        }
-        merged.push((start, end));
-    }
-
-    let count = merged
-        .iter()
-        .map(|&(start, end)| end - start + 1)
-        .sum::<u64>();
+    let (fresh, _) = parse_database(input)?;
 
-    Some(count)
+    u64::try_from(fresh.len()).ok()
 }
 
 #[cfg(test)]
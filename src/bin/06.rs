@@ -1,37 +1,58 @@
+use advent_of_code::parse::int;
+
 advent_of_code::solution!(6);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut columns: Vec<Vec<u64>> = Vec::new();
-    let mut sum = 0;
+/// Parses the problems of part one, written in rows below each other, into their operator and
+/// numbers.
+pub fn parse_problems(input: &str) -> Option<Vec<(u8, Vec<u64>)>> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let operators = lines.pop()?;
 
-    for line in input.lines() {
-        for (i, item) in line.split_whitespace().enumerate() {
-            if let Ok(value) = item.parse::<u64>() {
-                if i >= columns.len() {
-                    columns.resize_with(i + 1, Vec::new);
-                }
-                columns[i].push(value);
-            } else {
-                // Here we handle the operations
-                match item {
-                    "+" => {
-                        if let Some(col) = columns.get_mut(i) {
-                            let index: u64 = col.iter().sum();
-                            sum += index;
-                        }
-                    }
-                    "*" => {
-                        if let Some(col) = columns.get_mut(i) {
-                            let product: u64 = col.iter().product();
-                            sum += product;
-                        }
-                    }
-                    _ => todo!(),
-                }
-            }
+    let mut problems: Vec<(u8, Vec<u64>)> = operators
+        .split_whitespace()
+        .map(|op| matches!(op, "+" | "*").then(|| (op.as_bytes()[0], vec![])))
+        .collect::<Option<_>>()?;
+
+    for line in lines {
+        let numbers: Vec<u64> = line.split_whitespace().map(int).collect::<Option<_>>()?;
+        if numbers.len() != problems.len() {
+            return None;
+        }
+
+        for ((_, column), number) in problems.iter_mut().zip(numbers) {
+            column.push(number);
         }
     }
 
+    Some(problems)
+}
+
+/// Parses the worksheet of part two into rows of bytes of the same width, with the operators in
+/// the last row.
+pub fn parse_worksheet(input: &str) -> Option<Vec<&[u8]>> {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let (operators, numbers) = rows.split_last()?;
+
+    let width = operators.len();
+    let is_valid = rows.iter().all(|row| row.len() == width)
+        && operators.iter().all(|b| b" +*".contains(b))
+        && numbers
+            .iter()
+            .flat_map(|row| row.iter())
+            .all(|b| b == &b' ' || b.is_ascii_digit());
+
+    is_valid.then_some(rows)
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let sum = parse_problems(input)?
+        .into_iter()
+        .map(|(op, column)| match op {
+            b'+' => column.iter().sum::<u64>(),
+            _ => column.iter().product(),
+        })
+        .sum();
+
     Some(sum)
 }
 
@@ -46,7 +67,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 // Every number now is formed from the column, not the row.
 pub fn part_two(input: &str) -> Option<u64> {
     // we collect once and just map all as bytes.
-    let rows: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+    let rows = parse_worksheet(input)?;
     let (height, width) = (rows.len(), rows[0].len());
 
     let is_separator = |x: usize| -> bool { (0..height).all(|y| rows[y][x] == b' ') };
@@ -54,11 +75,11 @@ pub fn part_two(input: &str) -> Option<u64> {
     let operator = |x: usize| -> u8 { rows[height - 1][x] };
 
     // We don't use string allocations, because we calculate the number manually.
-    let parse_column = |x| {
+    let parse_column = |x: usize| {
         let mut num = 0u64;
         let mut found = false;
-        for y in 0..height - 1 {
-            let c = rows[y][x] as u8;
+        for row in &rows[..height - 1] {
+            let c = row[x];
             if c.is_ascii_digit() {
                 num = num * 10 + (c as u64 - '0' as u64); // We calculate the number directly and remove the '0' because we don't want to use the printable number.
                 found = true;
@@ -73,15 +94,16 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut start = 0;
 
     // here we parse on the fly, so we don't pre-allocate anything.
-    for x in 0..width {
-        if is_separator(x) {
+    // the end of the lines closes the last problem, just like a separator column.
+    for x in 0..=width {
+        if x == width || is_separator(x) {
             if start < x {
                 let op = operator(start);
                 let nums = (start..x).rev().filter_map(parse_column);
                 let result = match op {
                     b'*' => nums.product::<u64>(),
                     b'+' => nums.sum::<u64>(),
-                    _ => unreachable!(),
+                    _ => return None,
                 };
                 sum += result;
             }
@@ -107,4 +129,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(3_263_827));
     }
+
+    #[test]
+    fn test_part_two_last_problem() {
+        // the last problem ends with the lines instead of a separator column.
+        let result = part_two("1 2\n3 4\n+ *");
+        assert_eq!(result, Some(13 + 24));
+    }
 }
@@ -1,32 +1,46 @@
 use std::collections::HashSet;
 
+use advent_of_code::{
+    grid::{Grid, Pos},
+    memo::GridMemo,
+};
+
 advent_of_code::solution!(7);
 
 const SPLITTER: u8 = b'^';
 
+/// Parses the manifold, which has to be rectangular, along with the column the beam starts at
+/// in its first row.
+pub fn parse_manifold(input: &str) -> Option<(Grid<u8>, usize)> {
+    let width = input.lines().next().map_or(0, str::len);
+    if !input.lines().all(|line| line.len() == width) {
+        return None;
+    }
+
+    let grid = Grid::from_bytes(input);
+    match grid.find(&b'S')? {
+        (x, 0) => Some((grid, x)),
+        _ => None,
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
-    let init = lines[0]
-        .iter()
-        .position(|s| *s == b'S')
-        .expect("Expected the initialized bean");
+    let (grid, init) = parse_manifold(input)?;
 
     let mut beans = HashSet::from([init]);
     let mut splits = 0;
 
-    for &line in lines.iter().skip(1) {
+    for line in grid.rows().skip(1) {
         let mut bhs = HashSet::with_capacity(line.len());
         for &x in beans.iter() {
             match line[x] {
                 b'S' | b'.' => {
                     bhs.insert(x);
                 }
-                SPLITTER => {
-                    if x > 0 && x < line.len() {
-                        bhs.insert(x - 1);
-                        bhs.insert(x + 1);
-                        splits += 1;
-                    }
+                SPLITTER if x > 0 && x < line.len() => {
+                    bhs.insert(x - 1);
+                    bhs.insert(x + 1);
+                    splits += 1;
                 }
                 _ => {}
             };
@@ -42,47 +56,33 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(splits)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
-    let init = lines[0]
-        .iter()
-        .position(|s| *s == b'S')
-        .expect("Expected the initialized bean");
-
-    let h = lines.len();
-    let w = lines[0].len();
-
-    let mut dp = vec![vec![0u64; w]; h];
-    dp[0][init] = 1;
-    let mut splits = 0;
-
-    for y in 0..h - 1 {
-        for x in 0..w {
-            let v = dp[y][x];
-            if v == 0 {
-                continue;
-            }
-
-            match lines[y][x] {
-                SPLITTER => {
-                    splits += v;
-                    if x > 0 {
-                        dp[y + 1][x - 1] += v;
-                    }
-
-                    if x + 1 < w {
-                        dp[y + 1][x + 1] += v;
-                    }
-                }
+/// The number of timelines of a bean entering at `(x, y)`, one for every way it can leave the grid.
+fn timelines(grid: &Grid<u8>, memo: &mut GridMemo<u64>, (x, y): Pos) -> u64 {
+    if y == grid.height() {
+        return 1;
+    }
 
-                _ => {
-                    dp[y + 1][x] += v;
-                }
-            }
+    memo.get((x, y), |memo| match grid[(x, y)] {
+        SPLITTER => {
+            let left = x
+                .checked_sub(1)
+                .map_or(1, |x| timelines(grid, memo, (x, y + 1)));
+            let right = if x + 1 < grid.width() {
+                timelines(grid, memo, (x + 1, y + 1))
+            } else {
+                1
+            };
+            left + right
         }
-    }
+        _ => timelines(grid, memo, (x, y + 1)),
+    })
+}
 
-    Some(splits + 1)
+pub fn part_two(input: &str) -> Option<u64> {
+    let (grid, init) = parse_manifold(input)?;
+
+    let mut memo = GridMemo::for_grid(grid.width(), grid.height());
+    Some(timelines(&grid, &mut memo, (init, 0)))
 }
 
 #[cfg(test)]
@@ -100,4 +100,8 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(40));
     }
+
+    advent_of_code::example_tests!(single_splitter);
+    advent_of_code::example_tests!(two_splitters, converging_beams);
+    advent_of_code::example_tests!(1; no_splitters);
 }
@@ -0,0 +1,160 @@
+//! A [disjoint-set forest](https://en.wikipedia.org/wiki/Disjoint-set_data_structure), also
+//! known as union-find, for tracking which elements are connected.
+
+/// Partitions the elements `0..len` into disjoint sets, starting with one set per element.
+///
+/// Uses union by size and path compression, so all operations run in nearly constant time.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// The number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the representative of the set containing `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of bounds.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // point every element on the path directly at the root.
+        let mut current = x;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `false` if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same set.
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set containing `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The sizes of all sets, in no particular order.
+    #[must_use]
+    pub fn sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+            .collect()
+    }
+
+    /// The elements of all sets, ordered by their smallest element.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        let mut components: Vec<Vec<usize>> = vec![];
+        let mut index_of_root = vec![usize::MAX; self.len()];
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[index_of_root[root]].push(x);
+        }
+
+        components.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisjointSet;
+
+    #[test]
+    fn starts_with_singletons() {
+        let mut set = DisjointSet::new(3);
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.size_of(1), 1);
+        assert!(!set.connected(0, 1));
+    }
+
+    #[test]
+    fn merges_sets() {
+        let mut set = DisjointSet::new(5);
+        assert!(set.union(0, 1));
+        assert!(set.union(3, 1));
+        assert!(!set.union(0, 3));
+
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 2));
+        assert_eq!(set.size_of(3), 3);
+        assert_eq!(set.component_count(), 3);
+
+        let mut sizes = set.sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 3]);
+        assert_eq!(
+            set.components().collect::<Vec<_>>(),
+            [vec![0, 1, 3], vec![2], vec![4]]
+        );
+    }
+
+    #[test]
+    fn handles_many_elements() {
+        let len = 1_000_000;
+        let mut set = DisjointSet::new(len);
+        for x in 1..len {
+            set.union(x, x - 1);
+        }
+        assert_eq!(set.component_count(), 1);
+        assert_eq!(set.size_of(0), len);
+        assert!(set.connected(0, len - 1));
+    }
+}
@@ -0,0 +1,306 @@
+//! Geometry of rectilinear polygons, i.e. polygons whose edges are all horizontal or vertical,
+//! as they come up in puzzles that trace a loop on a grid.
+//!
+//! Vertices are integer points, and the polygon is usually thought of as a loop through the
+//! centers of grid tiles. The area enclosed by that loop and the number of tiles it covers differ,
+//! see [`tile_count`].
+use crate::grid::Grid;
+
+/// A point as `(x, y)`.
+pub type Point = (i64, i64);
+
+/// The area enclosed by a polygon, using the [shoelace formula](https://en.wikipedia.org/wiki/Shoelace_formula).
+///
+/// Vertices may be given in either orientation. The area of a polygon with integer vertices is a
+/// multiple of ½, which is rounded down. Rectilinear polygons always have an integral area.
+#[must_use]
+pub fn shoelace_area(vertices: &[Point]) -> u64 {
+    let twice_area: i64 = edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum();
+    twice_area.unsigned_abs() / 2
+}
+
+/// The length of the boundary of a rectilinear polygon.
+#[must_use]
+pub fn perimeter(vertices: &[Point]) -> u64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1.abs_diff(x2) + y1.abs_diff(y2))
+        .sum()
+}
+
+/// The number of integer points strictly inside a polygon with integer vertices, given its area
+/// and the number of integer points on its boundary, using [Pick's theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem).
+#[must_use]
+pub fn interior_points(area: u64, boundary_points: u64) -> u64 {
+    (area + 1).saturating_sub(boundary_points / 2)
+}
+
+/// The number of tiles covered by a loop through the tile centers, including the loop itself.
+///
+/// For a rectilinear polygon, every unit of the perimeter is one boundary tile.
+#[must_use]
+pub fn tile_count(vertices: &[Point]) -> u64 {
+    if vertices.is_empty() {
+        return 0;
+    }
+
+    let boundary = perimeter(vertices);
+    interior_points(shoelace_area(vertices), boundary) + boundary
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// A simple rectilinear polygon, indexed for fast queries about which tiles it covers.
+///
+/// The tiles are compressed to the coordinates of the vertices: every row and column of tiles
+/// that contains a vertex gets its own cell, as does every run of rows or columns in between.
+/// A tile is covered if it lies on the boundary or inside it, which is the same for every tile
+/// of a cell. A prefix sum over the cells that are not covered answers queries in `O(log n)`.
+#[derive(Clone, Debug)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Point>,
+    /// Start of every compressed column, the last one ends at the largest vertex `x`.
+    columns: Vec<i64>,
+    /// Start of every compressed row, the last one ends at the largest vertex `y`.
+    rows: Vec<i64>,
+    /// Number of uncovered cells above and to the left of each cell, exclusive.
+    uncovered: Grid<u32>,
+}
+
+impl RectilinearPolygon {
+    /// Indexes the polygon with the given vertices, in order along its boundary. Without
+    /// vertices, the polygon covers no tiles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if consecutive vertices are not on a horizontal or vertical line.
+    #[must_use]
+    pub fn new(vertices: Vec<Point>) -> Self {
+        for (a, b) in edges(&vertices) {
+            assert!(
+                a.0 == b.0 || a.1 == b.1,
+                "expected the edge from {a:?} to {b:?} to be horizontal or vertical"
+            );
+        }
+
+        let columns = compress(vertices.iter().map(|&(x, _)| x));
+        let rows = compress(vertices.iter().map(|&(_, y)| y));
+        let mut covered = Grid::new(columns.len(), rows.len(), false);
+        // marks where a ray to the right from the center of a tile crosses a vertical edge.
+        let mut crossings = Grid::new(columns.len(), rows.len(), false);
+
+        for (a, b) in edges(&vertices) {
+            let (cx1, cx2) = (cell(&columns, a.0.min(b.0)), cell(&columns, a.0.max(b.0)));
+            let (cy1, cy2) = (cell(&rows, a.1.min(b.1)), cell(&rows, a.1.max(b.1)));
+
+            for cy in cy1..=cy2 {
+                for cx in cx1..=cx2 {
+                    covered[(cx, cy)] = true;
+                }
+            }
+
+            // the rows the edge spans, excluding the last, so that a ray passing through a
+            // vertex is counted exactly once.
+            if a.0 == b.0 {
+                for cy in cy1..cy2 {
+                    crossings[(cx1, cy)] ^= true;
+                }
+            }
+        }
+
+        let mut uncovered = Grid::new(columns.len() + 1, rows.len() + 1, 0);
+        for cy in 0..rows.len() {
+            let mut inside = false;
+            for cx in 0..columns.len() {
+                let is_covered = covered[(cx, cy)] || inside;
+                inside ^= crossings[(cx, cy)];
+
+                uncovered[(cx + 1, cy + 1)] = uncovered[(cx, cy + 1)] + uncovered[(cx + 1, cy)]
+                    - uncovered[(cx, cy)]
+                    + u32::from(!is_covered);
+            }
+        }
+
+        Self {
+            vertices,
+            columns,
+            rows,
+            uncovered,
+        }
+    }
+
+    #[must_use]
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Whether the tile at `point` is on the boundary or inside the polygon.
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.contains_rect(point, point)
+    }
+
+    /// Whether all tiles of the rectangle spanned by two opposite corners are on the boundary or
+    /// inside the polygon.
+    #[must_use]
+    pub fn contains_rect(&self, (x1, y1): Point, (x2, y2): Point) -> bool {
+        let cells = |starts: &[i64], a: i64, b: i64| {
+            let (min, max) = (a.min(b), a.max(b));
+            let last = *starts.last()?;
+            // the last cell is a single row or column, as it contains a vertex.
+            if min < starts[0] || max > last {
+                return None;
+            }
+            Some((cell(starts, min), cell(starts, max) + 1))
+        };
+
+        let (Some((cx1, cx2)), Some((cy1, cy2))) =
+            (cells(&self.columns, x1, x2), cells(&self.rows, y1, y2))
+        else {
+            return false;
+        };
+
+        let u = &self.uncovered;
+        u[(cx2, cy2)] + u[(cx1, cy1)] == u[(cx1, cy2)] + u[(cx2, cy1)]
+    }
+
+    #[must_use]
+    pub fn area(&self) -> u64 {
+        shoelace_area(&self.vertices)
+    }
+
+    #[must_use]
+    pub fn perimeter(&self) -> u64 {
+        perimeter(&self.vertices)
+    }
+
+    /// The number of tiles on the boundary or inside the polygon.
+    #[must_use]
+    pub fn tile_count(&self) -> u64 {
+        tile_count(&self.vertices)
+    }
+}
+
+/// The start of every compressed cell along one axis.
+fn compress(values: impl Iterator<Item = i64>) -> Vec<i64> {
+    let mut values: Vec<i64> = values.collect();
+    values.sort_unstable();
+    values.dedup();
+
+    let mut starts = Vec::with_capacity(values.len() * 2);
+    for (i, &value) in values.iter().enumerate() {
+        starts.push(value);
+        if values.get(i + 1).is_some_and(|&next| next > value + 1) {
+            starts.push(value + 1);
+        }
+    }
+    starts
+}
+
+/// The index of the cell containing `value`, which must not lie before the first cell.
+fn cell(starts: &[i64], value: i64) -> usize {
+    starts.partition_point(|&start| start <= value) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RectilinearPolygon, interior_points, perimeter, shoelace_area, tile_count};
+
+    // ..........
+    // .#XXX#....
+    // .X...X....
+    // .X...#XX#.
+    // .X......X.
+    // .#XXXXXX#.
+    const L_SHAPE: [(i64, i64); 6] = [(1, 1), (5, 1), (5, 3), (8, 3), (8, 5), (1, 5)];
+
+    #[test]
+    fn measures_polygons() {
+        let square = [(0, 0), (2, 0), (2, 2), (0, 2)];
+        assert_eq!(shoelace_area(&square), 4);
+        assert_eq!(perimeter(&square), 8);
+        assert_eq!(interior_points(4, 8), 1);
+        assert_eq!(tile_count(&square), 9);
+
+        // orientation does not matter.
+        let mut reversed = L_SHAPE;
+        reversed.reverse();
+        assert_eq!(shoelace_area(&L_SHAPE), 22);
+        assert_eq!(shoelace_area(&reversed), 22);
+        assert_eq!(perimeter(&L_SHAPE), 22);
+        assert_eq!(tile_count(&L_SHAPE), 34);
+    }
+
+    #[test]
+    fn contains_tiles_on_and_inside_the_boundary() {
+        let polygon = RectilinearPolygon::new(L_SHAPE.to_vec());
+
+        let covered: usize = (0..10)
+            .flat_map(|y| (0..10).map(move |x| (x, y)))
+            .filter(|&point| polygon.contains(point))
+            .count();
+        assert_eq!(covered as u64, polygon.tile_count());
+
+        assert!(polygon.contains((1, 1)));
+        assert!(polygon.contains((3, 2)));
+        assert!(polygon.contains((7, 3)));
+        assert!(polygon.contains((7, 4)));
+        assert!(!polygon.contains((6, 2)));
+        assert!(!polygon.contains((0, 3)));
+        assert!(!polygon.contains((9, 4)));
+    }
+
+    #[test]
+    fn handles_polygons_without_vertices() {
+        let polygon = RectilinearPolygon::new(vec![]);
+        assert!(!polygon.contains((0, 0)));
+        assert!(!polygon.contains_rect((0, 0), (1, 1)));
+        assert_eq!(polygon.area(), 0);
+        assert_eq!(polygon.tile_count(), 0);
+
+        let point = RectilinearPolygon::new(vec![(3, 4)]);
+        assert!(point.contains((3, 4)));
+        assert!(!point.contains((3, 5)));
+        assert_eq!(point.tile_count(), 1);
+    }
+
+    #[test]
+    fn contains_rectangles() {
+        let polygon = RectilinearPolygon::new(L_SHAPE.to_vec());
+
+        assert!(polygon.contains_rect((1, 1), (5, 5)));
+        assert!(polygon.contains_rect((8, 5), (1, 3)));
+        assert!(polygon.contains_rect((2, 2), (2, 2)));
+        assert!(!polygon.contains_rect((1, 1), (8, 5)));
+        assert!(!polygon.contains_rect((5, 1), (8, 3)));
+        assert!(!polygon.contains_rect((0, 0), (2, 2)));
+    }
+
+    #[test]
+    fn handles_holes_in_the_ray() {
+        // a U shape, where rays through the arms cross the boundary four times.
+        let polygon = RectilinearPolygon::new(vec![
+            (0, 0),
+            (2, 0),
+            (2, 4),
+            (4, 4),
+            (4, 0),
+            (6, 0),
+            (6, 6),
+            (0, 6),
+        ]);
+
+        assert!(polygon.contains((1, 2)));
+        assert!(!polygon.contains((3, 2)));
+        assert!(polygon.contains((5, 2)));
+        assert!(polygon.contains_rect((0, 4), (6, 6)));
+        assert!(!polygon.contains_rect((0, 3), (6, 6)));
+        assert_eq!(polygon.tile_count(), 45);
+    }
+}
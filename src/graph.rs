@@ -0,0 +1,496 @@
+//! Graph search over implicit graphs, where the neighbours of a node are produced by a closure.
+//!
+//! Nodes can be anything hashable, e.g. grid positions or `(position, direction)` states. All
+//! searches accept several start nodes, which behaves as if a virtual node connected them all.
+//! Searches that return a path include both the start and the goal.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque, hash_map::Entry},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Finds a path with the fewest steps from one of the `starts` to a node that satisfies
+/// `success`, using breadth-first search.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut queue: VecDeque<N> = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = parents.entry(start.clone()) {
+            entry.insert(None);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if success(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// The fewest steps needed to reach every node that is reachable from one of the `starts`.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances: HashMap<N, usize> = HashMap::new();
+    let mut queue: VecDeque<(N, usize)> = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = distances.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Finds any path from one of the `starts` to a node that satisfies `success`, using
+/// depth-first search. The path is not necessarily the shortest one.
+pub fn dfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut stack: Vec<(N, Option<N>)> = starts.into_iter().map(|start| (start, None)).collect();
+    // visit the starts in the order they were given.
+    stack.reverse();
+
+    while let Some((node, parent)) = stack.pop() {
+        let Entry::Vacant(entry) = parents.entry(node.clone()) else {
+            continue;
+        };
+        entry.insert(parent);
+
+        if success(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                stack.push((next, Some(node.clone())));
+            }
+        }
+    }
+
+    None
+}
+
+/// All nodes that are reachable from one of the `starts`, including the starts.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut stack: Vec<N> = starts.into_iter().collect();
+    let mut visited: HashSet<N> = stack.iter().cloned().collect();
+
+    while let Some(node) = stack.pop() {
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    visited
+}
+
+/// The cost of an edge. Costs must not be negative, and `C::default()` must be zero.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+/// Finds a cheapest path from one of the `starts` to a node that satisfies `success`, using
+/// Dijkstra's algorithm. `neighbours` returns the neighbours of a node along with the cost of the
+/// edge to them. Returns the path and its total cost.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), success)
+}
+
+/// The cost of a cheapest path to every node that is reachable from one of the `starts`.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(starts);
+    search.run(neighbours, |_| C::default(), |_| false);
+    search
+        .visits
+        .into_iter()
+        .map(|visit| (visit.node, visit.cost))
+        .collect()
+}
+
+/// Finds a cheapest path like [`dijkstra`], but explores the nodes in order of their cost plus
+/// an estimate of the remaining cost, returned by `heuristic`.
+///
+/// The estimate must never exceed the actual remaining cost, otherwise the path found might not
+/// be the cheapest. The Manhattan distance to the goal is a good choice for grids.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(starts);
+    let goal = search.run(neighbours, heuristic, success)?;
+    Some(search.path_to(goal))
+}
+
+struct Visit<N, C> {
+    node: N,
+    cost: C,
+    parent: Option<usize>,
+}
+
+/// The state of a best-first search, storing nodes by index so that they do not need to be `Ord`.
+struct Search<N, C> {
+    visits: Vec<Visit<N, C>>,
+    indices: HashMap<N, usize>,
+    /// Nodes to explore as `(estimated total cost, cost so far, index)`.
+    queue: BinaryHeap<Reverse<(C, C, usize)>>,
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> Search<N, C> {
+    fn new(starts: impl IntoIterator<Item = N>) -> Self {
+        let mut search = Self {
+            visits: vec![],
+            indices: HashMap::new(),
+            queue: BinaryHeap::new(),
+        };
+        for start in starts {
+            search.relax(start, C::default(), None, C::default());
+        }
+        search
+    }
+
+    /// Records a path to `node` if it is cheaper than the best one so far.
+    fn relax(&mut self, node: N, cost: C, parent: Option<usize>, estimate: C) {
+        let index = match self.indices.entry(node) {
+            Entry::Occupied(entry) => {
+                let visit = &mut self.visits[*entry.get()];
+                if visit.cost <= cost {
+                    return;
+                }
+                visit.cost = cost;
+                visit.parent = parent;
+                *entry.get()
+            }
+            Entry::Vacant(entry) => {
+                self.visits.push(Visit {
+                    node: entry.key().clone(),
+                    cost,
+                    parent,
+                });
+                *entry.insert(self.visits.len() - 1)
+            }
+        };
+        self.queue.push(Reverse((cost + estimate, cost, index)));
+    }
+
+    /// Runs the search until a node satisfies `success`, returning its index.
+    fn run<I: IntoIterator<Item = (N, C)>>(
+        &mut self,
+        mut neighbours: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut success: impl FnMut(&N) -> bool,
+    ) -> Option<usize> {
+        while let Some(Reverse((_, cost, index))) = self.queue.pop() {
+            // nodes are queued again whenever a cheaper path is found, skip the outdated entries.
+            if cost > self.visits[index].cost {
+                continue;
+            }
+
+            let node = self.visits[index].node.clone();
+            if success(&node) {
+                return Some(index);
+            }
+
+            for (next, edge) in neighbours(&node) {
+                let estimate = heuristic(&next);
+                self.relax(next, cost + edge, Some(index), estimate);
+            }
+        }
+
+        None
+    }
+
+    fn path_to(&self, index: usize) -> (Vec<N>, C) {
+        let mut path = vec![];
+        let mut current = Some(index);
+        while let Some(index) = current {
+            path.push(self.visits[index].node.clone());
+            current = self.visits[index].parent;
+        }
+        path.reverse();
+        (path, self.visits[index].cost)
+    }
+}
+
+fn reconstruct_path<N: Eq + Hash + Clone>(parents: &HashMap<N, Option<N>>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Orders the `nodes` and all nodes reachable from them so that every node comes before its
+/// successors.
+///
+/// # Errors
+///
+/// Returns the nodes of a cycle, in order, if there is one.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    // `false` while a node is on the stack, `true` once all of its successors are sorted.
+    let mut finished: HashMap<N, bool> = HashMap::new();
+    let mut sorted = vec![];
+
+    for root in nodes {
+        if finished.contains_key(&root) {
+            continue;
+        }
+
+        finished.insert(root.clone(), false);
+        let successors_of_root = successors(&root).into_iter().collect::<Vec<_>>();
+        let mut stack = vec![(root, successors_of_root.into_iter())];
+
+        while let Some((node, pending)) = stack.last_mut() {
+            let Some(next) = pending.next() else {
+                finished.insert(node.clone(), true);
+                sorted.push(node.clone());
+                stack.pop();
+                continue;
+            };
+
+            match finished.get(&next) {
+                Some(true) => {}
+                Some(false) => {
+                    let start = stack.iter().position(|(n, _)| *n == next).unwrap();
+                    return Err(stack.drain(start..).map(|(n, _)| n).collect());
+                }
+                None => {
+                    finished.insert(next.clone(), false);
+                    let pending = successors(&next).into_iter().collect::<Vec<_>>();
+                    stack.push((next, pending.into_iter()));
+                }
+            }
+        }
+    }
+
+    sorted.reverse();
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        astar, bfs, bfs_distances, dfs, dijkstra, dijkstra_all, reachable, topological_sort,
+    };
+    use crate::grid::{Grid, Pos};
+
+    // S.#.
+    // .##.
+    // ...E
+    const MAZE: &str = "S.#.\n.##.\n...E";
+
+    fn open_neighbours(grid: &Grid<u8>) -> impl FnMut(&Pos) -> Vec<Pos> + '_ {
+        |&pos| {
+            grid.neighbours_4(pos)
+                .filter(|&next| grid[next] != b'#')
+                .collect()
+        }
+    }
+
+    #[test]
+    fn finds_shortest_paths_with_bfs() {
+        let grid = Grid::from_bytes(MAZE);
+        let start = grid.find(&b'S').unwrap();
+        let end = grid.find(&b'E').unwrap();
+
+        let path = bfs([start], open_neighbours(&grid), |&pos| pos == end).unwrap();
+        assert_eq!(path, [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2)]);
+
+        // from several starts, the closest one wins.
+        let path = bfs([start, (3, 0)], open_neighbours(&grid), |&pos| pos == end).unwrap();
+        assert_eq!(path, [(3, 0), (3, 1), (3, 2)]);
+
+        assert_eq!(
+            bfs([start], open_neighbours(&grid), |&pos| pos == (2, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn measures_distances_with_bfs() {
+        let grid = Grid::from_bytes(MAZE);
+        let distances = bfs_distances([(0, 0)], open_neighbours(&grid));
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(3, 2)], 5);
+        assert_eq!(distances[&(3, 0)], 7);
+        assert_eq!(distances.len(), 9);
+    }
+
+    #[test]
+    fn finds_paths_and_reachable_nodes_with_dfs() {
+        let grid = Grid::from_bytes(MAZE);
+        let path = dfs([(0, 0)], open_neighbours(&grid), |&pos| pos == (3, 0)).unwrap();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(3, 0)));
+        assert!(
+            path.windows(2)
+                .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1)
+        );
+
+        assert_eq!(reachable([(0, 0)], open_neighbours(&grid)).len(), 9);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // a direct edge that is more expensive than a detour.
+        let edges: HashMap<char, Vec<(char, u32)>> = HashMap::from([
+            ('a', vec![('b', 7), ('c', 2)]),
+            ('b', vec![('d', 1)]),
+            ('c', vec![('b', 3), ('d', 8)]),
+            ('d', vec![]),
+        ]);
+        let neighbours = |node: &char| edges[node].clone();
+
+        let (path, cost) = dijkstra(['a'], neighbours, |&node| node == 'd').unwrap();
+        assert_eq!(path, ['a', 'c', 'b', 'd']);
+        assert_eq!(cost, 6);
+
+        let costs = dijkstra_all(['a'], neighbours);
+        assert_eq!(
+            costs,
+            HashMap::from([('a', 0), ('b', 5), ('c', 2), ('d', 6)])
+        );
+
+        assert_eq!(dijkstra(['d'], neighbours, |&node| node == 'a'), None);
+    }
+
+    #[test]
+    fn agrees_with_dijkstra_using_astar() {
+        // moving down costs the digit of the next cell, moving sideways costs 1.
+        let grid = Grid::parse("1911\n1919\n1119\n9911", |b| u32::from(b - b'0'));
+        let end: Pos = (3, 3);
+        let neighbours = |&pos: &Pos| {
+            grid.neighbours_4(pos)
+                .map(|next| (next, if next.1 == pos.1 { 1 } else { grid[next] }))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(x, y): &Pos| (end.0.abs_diff(x) + end.1.abs_diff(y)) as u32;
+
+        let (_, expected) = dijkstra([(0, 0)], neighbours, |&pos| pos == end).unwrap();
+        let (path, cost) = astar([(0, 0)], neighbours, manhattan, |&pos| pos == end).unwrap();
+        assert_eq!(cost, expected);
+        assert_eq!(cost, 6);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&end));
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let edges: HashMap<u8, Vec<u8>> = HashMap::from([
+            (5, vec![11]),
+            (7, vec![11, 8]),
+            (3, vec![8, 10]),
+            (11, vec![2, 9, 10]),
+            (8, vec![9]),
+        ]);
+        let successors = |node: &u8| edges.get(node).cloned().unwrap_or_default();
+
+        let sorted = topological_sort([5, 7, 3], successors).unwrap();
+        assert_eq!(sorted.len(), 8);
+        let position = |node: u8| sorted.iter().position(|&n| n == node).unwrap();
+        for (&from, to) in &edges {
+            for &to in to {
+                assert!(
+                    position(from) < position(to),
+                    "{from} -> {to} in {sorted:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let edges: HashMap<u8, Vec<u8>> =
+            HashMap::from([(1, vec![2]), (2, vec![3]), (3, vec![4, 2]), (4, vec![])]);
+        let successors = |node: &u8| edges[node].clone();
+
+        assert_eq!(topological_sort([1], successors), Err(vec![2, 3]));
+        assert_eq!(topological_sort([4], successors), Ok(vec![4]));
+    }
+}
@@ -0,0 +1,390 @@
+//! A dense 2D grid, as used by most puzzles that come as a block of characters.
+//!
+//! Positions are `(x, y)` tuples, with `x` counting columns from the left and `y` counting rows
+//! from the top.
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A position in a [`Grid`], as `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours: up, right, down and left.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise starting at the top left.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, stored row by row in a single `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells is not a multiple of `width`.
+    #[must_use]
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "expected {} cells to fill rows of width {width}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per line, converting each byte with `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines do not all have the same length.
+    pub fn parse(input: &str, mut f: impl FnMut(u8) -> T) -> Self {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (y, line) in input.lines().enumerate() {
+            let expected = *width.get_or_insert(line.len());
+            assert_eq!(
+                line.len(),
+                expected,
+                "expected line {} to have {expected} characters",
+                y + 1
+            );
+            cells.extend(line.bytes().map(&mut f));
+        }
+
+        Self::from_vec(width.unwrap_or(0), cells)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` lies within the grid.
+    #[must_use]
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index_of(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `pos` by an offset, returning `None` if that leaves the grid.
+    #[must_use]
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The orthogonal neighbours of `pos` that lie within the grid.
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |offset| self.step(pos, offset))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that lie within the grid.
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |offset| self.step(pos, offset))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find_map(|(pos, cell)| (cell == value).then_some(pos))
+    }
+
+    /// The positions of all cells equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(pos, cell)| (cell == value).then_some(pos))
+    }
+
+    /// The cells of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        // skipped instead of sliced, as the cells of a grid without rows are empty.
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Applies `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Copies the grid with rows and columns swapped.
+    #[must_use]
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Copies the `width` × `height` section whose top left corner is at `pos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the section does not lie within the grid.
+    #[must_use]
+    pub fn sub_grid(&self, (x, y): Pos, width: usize, height: usize) -> Self
+    where
+        T: Clone,
+    {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "sub-grid of size {width}x{height} at ({x}, {y}) exceeds the grid"
+        );
+        let cells = (y..y + height)
+            .flat_map(|y| &self.row(y)[x..x + width])
+            .cloned()
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    fn index_of(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of raw bytes with one row per line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines do not all have the same length.
+    #[must_use]
+    pub fn from_bytes(input: &str) -> Self {
+        Self::parse(input, |b| b)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "position {pos:?} is out of bounds");
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "position {pos:?} is out of bounds");
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+/// Prints one row per line. Print a byte grid with `grid.map(|&b| b as char)`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn parses_rows() {
+        let grid = Grid::from_bytes(INPUT);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], b'b');
+        assert_eq!(grid[(2, 1)], b'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.row(1), b"def");
+    }
+
+    #[test]
+    fn parses_crlf_and_empty_input() {
+        assert_eq!(Grid::from_bytes("ab\r\ncd\r\n"), Grid::from_bytes("ab\ncd"));
+
+        let grid = Grid::from_bytes("");
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected line 2 to have 3 characters")]
+    fn rejects_ragged_rows() {
+        let _ = Grid::from_bytes("abc\nde");
+    }
+
+    #[test]
+    fn finds_neighbours_within_bounds() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours_4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours_8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::from_bytes("a.b\n.a.");
+        assert_eq!(grid.find(&b'b'), Some((2, 0)));
+        assert_eq!(grid.find(&b'z'), None);
+        assert_eq!(grid.find_all(&b'a').collect::<Vec<_>>(), [(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn views_columns_and_transposes() {
+        let grid = Grid::from_bytes(INPUT);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+
+        let transposed = grid.transposed();
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.row(2), b"cf");
+        assert_eq!(transposed.transposed(), grid);
+    }
+
+    #[test]
+    fn views_grids_without_rows_or_columns() {
+        let no_rows = Grid::new(3, 0, 0u8);
+        assert_eq!(no_rows.rows().count(), 0);
+        assert_eq!(no_rows.column(1).count(), 0);
+        assert_eq!(no_rows.columns().count(), 3);
+
+        let no_columns = Grid::new(0, 2, 0u8);
+        assert_eq!(no_columns.rows().count(), 2);
+        assert!(no_columns.rows().all(<[u8]>::is_empty));
+        assert_eq!(no_columns.columns().count(), 0);
+    }
+
+    #[test]
+    fn copies_sub_grids() {
+        let grid = Grid::from_bytes("abcd\nefgh\nijkl");
+        let sub = grid.sub_grid((1, 1), 2, 2);
+        assert_eq!(sub, Grid::from_bytes("fg\njk"));
+    }
+
+    #[test]
+    fn displays_rows() {
+        let mut grid = Grid::from_bytes(INPUT).map(|&b| b as char);
+        grid[(0, 0)] = '#';
+        assert_eq!(grid.to_string(), "#bc\ndef\n");
+    }
+}
@@ -0,0 +1,318 @@
+//! Sets of integers stored as sorted, non-overlapping ranges, for puzzles about ranges of IDs,
+//! seeds or coordinates that are far too large to enumerate.
+use std::{
+    fmt,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
+/// An integer type that can be stored in an [`IntervalSet`].
+pub trait Endpoint: Copy + Ord + fmt::Debug {
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_next(self) -> Option<Self>;
+
+    fn checked_prev(self) -> Option<Self>;
+
+    /// The number of values in `start..=end`, which must not be empty.
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked_next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                #[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+                fn span(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_endpoint!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers, stored as sorted ranges that neither overlap nor touch.
+///
+/// Ranges can be given with any of Rust's range types, e.g. `3..7`, `3..=6` or `10..`.
+/// Membership queries take `O(log n)` for `n` stored ranges.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    // inclusive bounds, so that ranges ending at `T::MAX` can be represented.
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// Adds all values in `range`, merging it with overlapping and adjacent ranges.
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = to_inclusive(&range) else {
+            return;
+        };
+
+        // ranges that end right before `start` or later are merged, ...
+        let from = self
+            .ranges
+            .partition_point(|&(_, e)| e.checked_next().is_some_and(|next| next < start));
+        // ... up to those that start right after `end`.
+        let to = self
+            .ranges
+            .partition_point(|&(s, _)| end.checked_next().is_none_or(|next| s <= next));
+
+        let merged = if from < to {
+            (
+                start.min(self.ranges[from].0),
+                end.max(self.ranges[to - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+
+        self.ranges.splice(from..to, [merged]);
+    }
+
+    /// Removes all values in `range`, splitting ranges that it lies within.
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = to_inclusive(&range) else {
+            return;
+        };
+
+        let from = self.ranges.partition_point(|&(_, e)| e < start);
+        let to = self.ranges.partition_point(|&(s, _)| s <= end);
+        if from >= to {
+            return;
+        }
+
+        let (first, last) = (self.ranges[from], self.ranges[to - 1]);
+        let left = start.checked_prev().filter(|_| first.0 < start);
+        let right = end.checked_next().filter(|_| last.1 > end);
+
+        let remainder = left
+            .map(|prev| (first.0, prev))
+            .into_iter()
+            .chain(right.map(|next| (next, last.1)));
+
+        self.ranges.splice(from..to, remainder);
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// All values that are in `self` or `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    /// All values that are in both `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// All values that are in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The number of values in the set.
+    ///
+    /// This is a `u128`, as a set of all `u64` values contains `u64::MAX + 1` values.
+    #[must_use]
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| T::span(start, end))
+            .sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of disjoint ranges the set consists of.
+    #[must_use]
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The disjoint ranges of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+}
+
+impl<T: Endpoint> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Endpoint, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Endpoint> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Converts a range to inclusive bounds, or `None` if it is empty.
+fn to_inclusive<T: Endpoint>(range: &impl RangeBounds<T>) -> Option<(T, T)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_next()?,
+        Bound::Unbounded => T::MIN,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) => end.checked_prev()?,
+        Bound::Unbounded => T::MAX,
+    };
+    (start <= end).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    fn ranges<T: super::Endpoint>(set: &IntervalSet<T>) -> Vec<(T, T)> {
+        set.iter().map(|r| r.into_inner()).collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        let set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18, 6..=6]
+            .into_iter()
+            .collect();
+        assert_eq!(ranges(&set), [(3, 6), (10, 20)]);
+        assert_eq!(set.len(), 15);
+    }
+
+    #[test]
+    fn respects_inclusive_and_exclusive_bounds() {
+        let mut set = IntervalSet::new();
+        set.insert(1..4);
+        set.insert(4..=4);
+        set.insert(6..6);
+        assert_eq!(ranges(&set), [(1, 4)]);
+
+        assert!(!set.contains(0));
+        assert!(set.contains(1));
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert!(!set.contains(6));
+    }
+
+    #[test]
+    fn removes_ranges() {
+        let mut set: IntervalSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        set.remove(5..25);
+        assert_eq!(ranges(&set), [(0, 4), (25, 30)]);
+
+        set.remove(..=0);
+        set.remove(30..);
+        assert_eq!(ranges(&set), [(1, 4), (25, 29)]);
+
+        set.remove(2..=2);
+        assert_eq!(ranges(&set), [(1, 1), (3, 4), (25, 29)]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: IntervalSet<u32> = [0..=10, 20..=30].into_iter().collect();
+        let b: IntervalSet<u32> = [5..=25].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), [(0, 30)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(5, 10), (20, 25)]);
+        assert_eq!(ranges(&a.difference(&b)), [(0, 4), (26, 30)]);
+        assert_eq!(ranges(&b.difference(&a)), [(11, 19)]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn handles_values_at_u64_max() {
+        let mut set = IntervalSet::new();
+        set.insert(u64::MAX - 1..=u64::MAX);
+        set.insert(u64::MAX..);
+        set.insert(u64::MAX..u64::MAX);
+        assert_eq!(ranges(&set), [(u64::MAX - 1, u64::MAX)]);
+        assert!(set.contains(u64::MAX));
+
+        set.insert(0..=0);
+        assert_eq!(set.range_count(), 2);
+
+        set.insert(..);
+        assert_eq!(set.len(), u128::from(u64::MAX) + 1);
+
+        set.remove(u64::MAX..);
+        assert!(!set.contains(u64::MAX));
+        assert_eq!(set.len(), u128::from(u64::MAX));
+
+        set.remove(..);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn handles_signed_values() {
+        let set: IntervalSet<i64> = [i64::MIN..=-1, 0..=i64::MAX].into_iter().collect();
+        assert_eq!(ranges(&set), [(i64::MIN, i64::MAX)]);
+        assert_eq!(set.len(), 1 << 64);
+    }
+}
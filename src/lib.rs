@@ -1,3 +1,15 @@
+pub mod disjoint_set;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval_set;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod spatial;
 pub mod template;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 // Use this file to add helper functions and additional modules.
@@ -1,14 +1,15 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, read, scaffold, solve, status, time, unscaffold,
+};
+use advent_of_code::template::{EXIT_USAGE, Error, output};
 use args::{AppArguments, parse};
+use std::process;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{Day, output};
 
     pub enum AppArguments {
         Download {
@@ -21,21 +22,32 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
+            template: Option<String>,
+            part_two: bool,
+        },
+        Unscaffold {
+            day: Day,
+            archive: bool,
+            dry_run: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            record: bool,
         },
         All {
             release: bool,
+            record: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
         },
+        Status,
         #[cfg(feature = "today")]
         Today,
     }
@@ -43,9 +55,16 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
+        // global flags, valid for every command.
+        output::set_json(args.contains("--json"));
+        if let Some(choice) = args.opt_value_from_str("--color")? {
+            output::set_color(choice);
+        }
+
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                record: args.contains("--record"),
             },
             Some("time") => {
                 let all = args.contains("--all");
@@ -67,78 +86,104 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                dry_run: args.contains("--dry-run"),
+                template: args.opt_value_from_str("--template")?,
+                part_two: args.contains("--part-two"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some(command @ ("unscaffold" | "archive")) => AppArguments::Unscaffold {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                archive: command == "archive",
+                dry_run: args.contains("--dry-run"),
             },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let submit = args.opt_value_from_str("--submit")?;
+                if submit.is_some_and(|part| !matches!(part, 1 | 2)) {
+                    return Err("--submit expects the part to submit, 1 or 2.".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release: args.contains("--release"),
+                    submit,
+                    dhat: args.contains("--dhat"),
+                    record: args.contains("--record"),
+                }
+            }
+            Some("status") => AppArguments::Status,
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("Unknown command: {x}").into()),
+            None => return Err("No command specified.".into()),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            output::warning(&format!("unknown argument(s): {remaining:?}."));
         }
 
         Ok(app_args)
     }
 }
 
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All { release, record } => all::handle(release, record),
+        AppArguments::Time { day, all, store } => time::handle(day, all, store),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            dry_run,
+            template,
+            part_two,
+        } => {
+            if part_two {
+                return scaffold::handle_part_two(day);
+            }
+
+            scaffold::handle(day, overwrite, dry_run, template.as_deref())?;
+            if download && !dry_run {
+                download::handle(day)?;
+            }
+            Ok(())
+        }
+        AppArguments::Unscaffold {
+            day,
+            archive,
+            dry_run,
+        } => unscaffold::handle(day, archive, dry_run),
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            record,
+        } => solve::handle(day, release, dhat, submit, record),
+        AppArguments::Status => status::handle(),
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            let day = Day::today().ok_or(Error::NotAdvent)?;
+            scaffold::handle(day, false, false, None)?;
+            download::handle(day)?;
+            read::handle(day)
+        }
+    }
+}
+
 fn main() {
-    match parse() {
+    let args = match parse() {
+        Ok(args) => args,
         Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+            output::error(&err.to_string());
+            process::exit(EXIT_USAGE);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
-        },
     };
+
+    if let Err(err) = run(args) {
+        output::error(&err.to_string());
+        process::exit(err.exit_code());
+    }
 }
@@ -0,0 +1,381 @@
+//! Number theory helpers: digits, divisibility, modular arithmetic and integer roots.
+//!
+//! Functions are `const` where possible and use wider intermediate types or checked arithmetic,
+//! so they work for the whole range of `u64`.
+
+/// `10ⁱ` for every power that fits in a `u64`.
+pub const POWERS_OF_TEN: [u64; 20] = {
+    let mut powers = [1; 20];
+    let mut i = 1;
+    while i < powers.len() {
+        powers[i] = powers[i - 1] * 10;
+        i += 1;
+    }
+    powers
+};
+
+/// The number of decimal digits of `n`, where `0` has one digit.
+#[must_use]
+pub const fn digit_count(n: u64) -> u32 {
+    match n.checked_ilog10() {
+        Some(log) => log + 1,
+        None => 1,
+    }
+}
+
+/// The decimal digits of `n`, most significant first.
+#[must_use]
+pub const fn digits(n: u64) -> Digits {
+    Digits {
+        n,
+        len: digit_count(n),
+    }
+}
+
+/// An iterator over the decimal digits of a number, see [`digits`].
+#[derive(Clone, Debug)]
+pub struct Digits {
+    n: u64,
+    len: u32,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.len = self.len.checked_sub(1)?;
+        let power = POWERS_OF_TEN[self.len as usize];
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.n / power) as u8;
+        self.n %= power;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+/// The number whose digits are those of `a` followed by those of `b`, e.g. `12 ‖ 345 = 12345`.
+/// Returns `None` on overflow.
+#[must_use]
+pub const fn concat(a: u64, b: u64) -> Option<u64> {
+    // a shift by 20 digits doesn't fit, but is not needed if there is nothing to shift.
+    match 10u64.checked_pow(digit_count(b)) {
+        Some(shift) => match a.checked_mul(shift) {
+            Some(shifted) => shifted.checked_add(b),
+            None => None,
+        },
+        None if a == 0 => Some(b),
+        None => None,
+    }
+}
+
+/// The greatest common divisor of `a` and `b`, where `gcd(0, 0) = 0`.
+#[must_use]
+pub const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, or `None` if it overflows.
+#[must_use]
+pub const fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The greatest common divisor `g` of `a` and `b`, along with coefficients `x` and `y` such that
+/// `a·x + b·y = g`.
+///
+/// # Panics
+///
+/// If `a` or `b` is `i64::MIN`, as their divisors may not fit in an `i64`.
+#[must_use]
+pub const fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    assert!(
+        a != i64::MIN && b != i64::MIN,
+        "expected a and b to be above i64::MIN"
+    );
+
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    // the coefficients are bounded by |a| and |b|, so they fit again.
+    (g as i64, x as i64, y as i64)
+}
+
+/// [`extended_gcd`] on wider integers, with a non-negative `g`.
+const fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `base^exp mod modulus`.
+///
+/// # Panics
+///
+/// If `modulus` is zero.
+#[must_use]
+pub const fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "expected a non-zero modulus");
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    // the result is below the modulus, so it fits.
+    result as u64
+}
+
+/// The `x` in `0..modulus` with `a·x ≡ 1 (mod modulus)`, or `None` if `a` and `modulus` are not
+/// coprime.
+///
+/// # Panics
+///
+/// If `modulus` is zero.
+#[must_use]
+pub const fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    assert!(modulus != 0, "expected a non-zero modulus");
+
+    let (g, x, _) = extended_gcd_wide(a as i128, modulus as i128);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(modulus as i128) as u64)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the [Chinese remainder theorem](https://en.wikipedia.org/wiki/Chinese_remainder_theorem),
+/// returning the smallest solution and the least common multiple of the moduli, which all
+/// solutions are congruent modulo.
+///
+/// The moduli don't need to be coprime. Returns `None` if the congruences contradict each other
+/// or the least common multiple overflows.
+///
+/// # Panics
+///
+/// If a modulus is zero.
+#[must_use]
+pub const fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0u128, 1u128);
+
+    let mut i = 0;
+    while i < congruences.len() {
+        let (residue, modulus) = congruences[i];
+        assert!(modulus != 0, "expected a non-zero modulus");
+        let (residue, modulus) = (residue as u128 % modulus as u128, modulus as u128);
+
+        // find k with x + k·m ≡ residue (mod modulus), i.e. k·m/g ≡ (residue - x)/g (mod modulus/g)
+        let g = gcd_wide(m, modulus);
+        let difference = (residue + modulus - x % modulus) % modulus;
+        if difference % g != 0 {
+            return None;
+        }
+
+        let reduced = modulus / g;
+        let (_, inverse, _) = extended_gcd_wide((m / g % reduced) as i128, reduced as i128);
+        let inverse = inverse.rem_euclid(reduced as i128) as u128;
+        let k = (difference / g) % reduced * inverse % reduced;
+
+        let next_m = m * reduced;
+        if next_m > u64::MAX as u128 {
+            return None;
+        }
+        x = (x + k * m) % next_m;
+        m = next_m;
+        i += 1;
+    }
+
+    Some((x as u64, m as u64))
+}
+
+const fn gcd_wide(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// All divisors of `n` in ascending order, or none for zero.
+#[must_use]
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut small = vec![];
+    let mut large = vec![];
+
+    for d in (1..=n.isqrt()).filter(|d| n.is_multiple_of(*d)) {
+        small.push(d);
+        if d != n / d {
+            large.push(n / d);
+        }
+    }
+
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// The largest `r` with `rᵏ ≤ n`.
+///
+/// # Panics
+///
+/// If `k` is zero.
+#[must_use]
+pub const fn nth_root(n: u64, k: u32) -> u64 {
+    assert!(k != 0, "expected a positive root");
+
+    if k == 1 {
+        return n;
+    }
+
+    // the root has at most 64 / k bits, set them from the top while the power stays below n.
+    let mut root = 0u64;
+    let mut bit = 64 / k + 1;
+    while bit > 0 {
+        bit -= 1;
+        let candidate = root | 1 << bit;
+        if let Some(power) = candidate.checked_pow(k)
+            && power <= n
+        {
+            root = candidate;
+        }
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        POWERS_OF_TEN, concat, crt, digit_count, digits, divisors, extended_gcd, gcd, lcm,
+        mod_inverse, mod_pow, nth_root,
+    };
+
+    #[test]
+    fn counts_digits() {
+        assert_eq!(POWERS_OF_TEN[19], 10_000_000_000_000_000_000);
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+
+        assert_eq!(digits(0).collect::<Vec<_>>(), [0]);
+        assert_eq!(digits(1203).collect::<Vec<_>>(), [1, 2, 0, 3]);
+        assert_eq!(digits(u64::MAX).len(), 20);
+        assert_eq!(digits(u64::MAX).last(), Some(5));
+    }
+
+    #[test]
+    fn concatenates_numbers() {
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(12, 0), Some(120));
+        assert_eq!(concat(0, 7), Some(7));
+        assert_eq!(concat(1_844_674_407, 3_709_551_615), Some(u64::MAX));
+        assert_eq!(concat(1_844_674_407, 3_709_551_616), None);
+        assert_eq!(concat(u64::MAX, 1), None);
+        assert_eq!(concat(0, u64::MAX), Some(u64::MAX));
+        assert_eq!(concat(1, 10_000_000_000_000_000_000), None);
+    }
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(u64::MAX, u64::MAX - 1), 1);
+
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX, 2), None);
+
+        for (a, b) in [
+            (240, 46),
+            (-240, 46),
+            (7, 0),
+            (0, -7),
+            (i64::MAX, i64::MAX - 1),
+        ] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g.unsigned_abs(), gcd(a.unsigned_abs(), b.unsigned_abs()));
+            assert_eq!(
+                i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y),
+                i128::from(g)
+            );
+        }
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, u64::MAX, u64::MAX), u64::MAX - 1);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(2, u64::MAX), Some(u64::MAX / 2 + 1));
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that are not coprime.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        // residues above their modulus.
+        assert_eq!(crt(&[(5, 3), (8, 5)]), Some((8, 15)));
+
+        let large = [(1, 4_294_967_291), (2, 4_294_967_279)];
+        let (x, m) = crt(&large).unwrap();
+        assert_eq!(m, 4_294_967_291 * 4_294_967_279);
+        assert_eq!((x % 4_294_967_291, x % 4_294_967_279), (1, 2));
+        assert_eq!(crt(&[(0, u64::MAX), (0, 2)]), None);
+    }
+
+    #[test]
+    fn finds_divisors() {
+        assert!(divisors(0).is_empty());
+        assert_eq!(divisors(1), [1]);
+        assert_eq!(divisors(36), [1, 2, 3, 4, 6, 9, 12, 18, 36]);
+        assert_eq!(divisors(97), [1, 97]);
+    }
+
+    #[test]
+    fn computes_integer_roots() {
+        const CUBE_ROOT: u64 = nth_root(1000, 3);
+        assert_eq!(CUBE_ROOT, 10);
+
+        assert_eq!(nth_root(0, 2), 0);
+        assert_eq!(nth_root(15, 2), 3);
+        assert_eq!(nth_root(16, 2), 4);
+        assert_eq!(nth_root(u64::MAX, 1), u64::MAX);
+        assert_eq!(nth_root(u64::MAX, 2), u64::MAX.isqrt());
+        assert_eq!(nth_root(u64::MAX, 3), 2_642_245);
+        assert_eq!(nth_root(u64::MAX, 64), 1);
+        assert_eq!(nth_root(1 << 63, 63), 2);
+    }
+}
@@ -0,0 +1,255 @@
+//! Caches for memoizing recursive functions, keyed by hashable arguments or by grid positions.
+//!
+//! A memoized function takes the cache as its first argument and wraps its body in
+//! [`Memo::get`], which passes the cache back in for the recursive calls:
+//!
+//! ```ignore
+//! fn paths(memo: &mut Memo<(usize, usize), u64>, (x, y): (usize, usize)) -> u64 {
+//!     memo.get((x, y), |memo| match (x, y) {
+//!         (0, _) | (_, 0) => 1,
+//!         _ => paths(memo, (x - 1, y)) + paths(memo, (x, y - 1)),
+//!     })
+//! }
+//! ```
+//!
+//! Debug builds count cache hits and misses, see [`Memo::stats`]. Set the `AOC_MEMO_STATS`
+//! environment variable to print them when a cache is dropped.
+use std::{collections::HashMap, env, fmt, hash::Hash, marker::PhantomData};
+
+use crate::grid::{Grid, Pos};
+
+/// Where a [`Memo`] keeps its values.
+pub trait Store<K, V> {
+    fn lookup(&self, key: &K) -> Option<&V>;
+
+    fn store(&mut self, key: K, value: V);
+
+    fn clear(&mut self);
+}
+
+impl<K: Hash + Eq, V> Store<K, V> for HashMap<K, V> {
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn store(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self);
+    }
+}
+
+/// A dense table with one slot per grid position.
+impl<V> Store<Pos, V> for Grid<Option<V>> {
+    fn lookup(&self, key: &Pos) -> Option<&V> {
+        self.get(*key)?.as_ref()
+    }
+
+    fn store(&mut self, key: Pos, value: V) {
+        self[key] = Some(value);
+    }
+
+    fn clear(&mut self) {
+        for pos in self.positions() {
+            self[pos] = None;
+        }
+    }
+}
+
+/// Cache hits and misses of a [`Memo`]. Only counted in debug builds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// The share of lookups that were answered from the cache, between 0 and 1.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// Memoizes the results of a function by its arguments `K`.
+///
+/// Uses a [`HashMap`] by default, or a dense table for grid positions, see [`GridMemo`].
+#[derive(Clone, Debug)]
+pub struct Memo<K, V, S = HashMap<K, V>> {
+    store: S,
+    stats: CacheStats,
+    marker: PhantomData<(K, V)>,
+}
+
+/// A [`Memo`] keyed by the positions of a grid, which avoids hashing.
+pub type GridMemo<V> = Memo<Pos, V, Grid<Option<V>>>;
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_store(HashMap::new())
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone> GridMemo<V> {
+    /// A cache for the positions of a `width` by `height` grid.
+    #[must_use]
+    pub fn for_grid(width: usize, height: usize) -> Self {
+        Self::with_store(Grid::new(width, height, None))
+    }
+}
+
+impl<K, V: Clone, S: Store<K, V>> Memo<K, V, S> {
+    #[must_use]
+    pub fn with_store(store: S) -> Self {
+        Self {
+            store,
+            stats: CacheStats::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// The cached value for `key`, or the result of `compute`, which is cached for later calls.
+    /// `compute` is passed the cache again for recursive calls.
+    ///
+    /// # Panics
+    ///
+    /// If a [`GridMemo`] is passed a position outside its grid.
+    pub fn get(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.store.lookup(&key) {
+            if cfg!(debug_assertions) {
+                self.stats.hits += 1;
+            }
+            return value.clone();
+        }
+
+        if cfg!(debug_assertions) {
+            self.stats.misses += 1;
+        }
+
+        let value = compute(self);
+        self.store.store(key, value.clone());
+        value
+    }
+
+    /// The cached value for `key`, without computing it.
+    #[must_use]
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.store.lookup(key)
+    }
+
+    /// Removes all cached values, e.g. before solving for a different input.
+    pub fn clear(&mut self) {
+        self.store.clear();
+    }
+
+    /// The cache hits and misses so far. Always zero in release builds.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+impl<K, V, S> Drop for Memo<K, V, S> {
+    fn drop(&mut self) {
+        let is_enabled = env::var_os("AOC_MEMO_STATS").is_some_and(|x| !x.is_empty() && x != "0");
+        if cfg!(debug_assertions) && is_enabled {
+            eprintln!("Memo: {}", self.stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheStats, GridMemo, Memo};
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fibonacci(memo, n - 1) + fibonacci(memo, n - 2)
+            }
+        })
+    }
+
+    fn paths(memo: &mut GridMemo<u64>, (x, y): (usize, usize)) -> u64 {
+        memo.get((x, y), |memo| match (x, y) {
+            (0, _) | (_, 0) => 1,
+            _ => paths(memo, (x - 1, y)) + paths(memo, (x, y - 1)),
+        })
+    }
+
+    #[test]
+    fn memoizes_recursive_functions() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.peek(&10), Some(&55));
+        assert_eq!(memo.peek(&91), None);
+
+        if cfg!(debug_assertions) {
+            // every value is computed once, and `fibonacci(n - 2)` is a hit for every n above 2.
+            assert_eq!(
+                memo.stats(),
+                CacheStats {
+                    hits: 88,
+                    misses: 91
+                }
+            );
+        }
+
+        memo.clear();
+        assert_eq!(memo.peek(&10), None);
+    }
+
+    #[test]
+    fn memoizes_grid_positions() {
+        let mut memo = GridMemo::for_grid(17, 17);
+        assert_eq!(paths(&mut memo, (16, 16)), 601_080_390);
+        assert_eq!(paths(&mut memo, (2, 2)), 6);
+        assert_eq!(memo.peek(&(1, 1)), Some(&2));
+
+        memo.clear();
+        assert_eq!(memo.peek(&(1, 1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn rejects_positions_outside_the_grid() {
+        let mut memo = GridMemo::for_grid(2, 2);
+        memo.get((2, 0), |_| 0);
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = CacheStats { hits: 3, misses: 1 };
+        assert_eq!(stats.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+        assert!(CacheStats::default().hit_rate().abs() < f64::EPSILON);
+    }
+}
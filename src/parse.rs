@@ -0,0 +1,287 @@
+//! Fast, allocation-free parsing of the integers that puzzle inputs are made of.
+use std::any::type_name;
+
+/// An integer type that can be parsed from ASCII digits.
+pub trait Integer: Copy {
+    /// Parses an optional sign followed by decimal digits. Returns `None` if `bytes` contains
+    /// anything else or if the value does not fit.
+    fn from_ascii(bytes: &[u8]) -> Option<Self>;
+
+    /// Whether a `-` in front of the digits is part of the number.
+    const SIGNED: bool;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = false;
+
+                fn from_ascii(bytes: &[u8]) -> Option<Self> {
+                    let digits = bytes.strip_prefix(b"+").unwrap_or(bytes);
+                    Self::try_from(parse_digits(digits)?).ok()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = true;
+
+                fn from_ascii(bytes: &[u8]) -> Option<Self> {
+                    let (negative, digits) = match bytes {
+                        [b'-', digits @ ..] => (true, digits),
+                        [b'+', digits @ ..] => (false, digits),
+                        digits => (false, digits),
+                    };
+                    let magnitude = i128::from(parse_digits(digits)?);
+                    Self::try_from(if negative { -magnitude } else { magnitude }).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+impl_signed!(i8, i16, i32, i64, isize);
+
+/// The largest number of digits that always fits in a `u64`.
+const SAFE_DIGITS: usize = 19;
+
+/// Parses decimal digits, eight at a time where possible.
+fn parse_digits(digits: &[u8]) -> Option<u64> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    if digits.len() > SAFE_DIGITS {
+        return digits.iter().try_fold(0u64, |n, &b| {
+            let digit = b.wrapping_sub(b'0');
+            (digit < 10).then_some(())?;
+            n.checked_mul(10)?.checked_add(u64::from(digit))
+        });
+    }
+
+    let mut chunks = digits.chunks_exact(8);
+    let mut n = 0u64;
+
+    for chunk in &mut chunks {
+        n = n * 100_000_000 + parse_eight_digits(chunk.try_into().unwrap())?;
+    }
+
+    for &b in chunks.remainder() {
+        let digit = b.wrapping_sub(b'0');
+        if digit >= 10 {
+            return None;
+        }
+        n = n * 10 + u64::from(digit);
+    }
+
+    Some(n)
+}
+
+/// Parses eight digits at once by treating them as the bytes of a `u64` ("SWAR").
+fn parse_eight_digits(chunk: [u8; 8]) -> Option<u64> {
+    const ZEROES: u64 = 0x3030_3030_3030_3030;
+    const HIGH_NIBBLES: u64 = 0xF0F0_F0F0_F0F0_F0F0;
+
+    let value = u64::from_le_bytes(chunk);
+    // every byte must be in `0x30..=0x39`, i.e. stay below `0x40` when adding 6.
+    let is_digits = value & HIGH_NIBBLES == ZEROES
+        && value.wrapping_add(0x0606_0606_0606_0606) & HIGH_NIBBLES == ZEROES;
+    if !is_digits {
+        return None;
+    }
+
+    // combine neighbouring digits into pairs, then pairs into groups of four and so on.
+    let value = value - ZEROES;
+    let value = (value * 10 + (value >> 8)) & 0x00FF_00FF_00FF_00FF;
+    let value = (value * 100 + (value >> 16)) & 0x0000_FFFF_0000_FFFF;
+    Some((value * 10_000 + (value >> 32)) & 0xFFFF_FFFF)
+}
+
+/// Parses a whole string or byte slice as an integer, like [`str::parse`].
+pub fn int<T: Integer>(s: impl AsRef<[u8]>) -> Option<T> {
+    T::from_ascii(s.as_ref())
+}
+
+/// All integers in `s`, ignoring anything in between, e.g. `[1, -2, 3]` for `x=1, y=-2..3`.
+///
+/// For signed types, a `-` is part of a number unless it directly follows a digit, so that
+/// ranges like `1-5` are parsed as two positive numbers.
+///
+/// # Panics
+///
+/// Panics if a number does not fit in `T`.
+pub fn ints<T: Integer>(s: &str) -> impl Iterator<Item = T> + '_ {
+    let bytes = s.as_bytes();
+    let mut pos = 0;
+
+    std::iter::from_fn(move || {
+        let start = pos + bytes[pos..].iter().position(u8::is_ascii_digit)?;
+        let end = bytes[start..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| start + len);
+        pos = end;
+
+        let is_negative = T::SIGNED
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit());
+        let number = &bytes[start - usize::from(is_negative)..end];
+
+        Some(T::from_ascii(number).unwrap_or_else(|| {
+            panic!(
+                "expected {} to fit in {}",
+                String::from_utf8_lossy(number),
+                type_name::<T>()
+            )
+        }))
+    })
+}
+
+/// Parses two integers separated by `separator`, e.g. `11-22` or `3 -> 4`.
+/// Whitespace around the integers is ignored.
+pub fn pair<T: Integer>(s: &str, separator: &str) -> Option<(T, T)> {
+    let (a, b) = split_once(s, separator)?;
+    Some((int(a.trim_ascii())?, int(b.trim_ascii())?))
+}
+
+/// Parses three integers separated by `separator`, e.g. `1,2,3`.
+/// Whitespace around the integers is ignored.
+pub fn triple<T: Integer>(s: &str, separator: &str) -> Option<(T, T, T)> {
+    let (a, rest) = split_once(s, separator)?;
+    let (b, c) = split_once(rest, separator)?;
+    Some((
+        int(a.trim_ascii())?,
+        int(b.trim_ascii())?,
+        int(c.trim_ascii())?,
+    ))
+}
+
+/// Like [`str::split_once`], but faster for separators of a single byte.
+fn split_once<'a>(s: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    match separator.as_bytes() {
+        &[byte] => {
+            let index = s.bytes().position(|b| b == byte)?;
+            Some((&s[..index], &s[index + 1..]))
+        }
+        _ => s.split_once(separator),
+    }
+}
+
+/// Splits an input into the sections separated by blank lines, for both LF and CRLF line endings.
+/// Lines that only contain spaces are not blank, as they may be rows of a grid. Sections do not
+/// include the line break at their end.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+
+    std::iter::from_fn(move || {
+        let current = rest;
+        let (mut start, mut end, mut offset) = (None, 0, 0);
+
+        for line in current.split_inclusive('\n') {
+            if line.trim_end_matches(['\r', '\n']).is_empty() {
+                if start.is_some() {
+                    break;
+                }
+            } else {
+                start.get_or_insert(offset);
+                end = offset + line.trim_end_matches(['\r', '\n']).len();
+            }
+            offset += line.len();
+        }
+
+        rest = &current[offset..];
+        start.map(|start| &current[start..end])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{int, ints, pair, parse_digits, sections, triple};
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(int::<u64>("0"), Some(0));
+        assert_eq!(int::<u64>("+42"), Some(42));
+        assert_eq!(int::<i32>("-42"), Some(-42));
+        assert_eq!(int::<u8>(b"255"), Some(255));
+        assert_eq!(int::<u8>("256"), None);
+        assert_eq!(int::<i8>("-128"), Some(-128));
+        assert_eq!(int::<i8>("128"), None);
+        assert_eq!(int::<u64>("-1"), None);
+        assert_eq!(int::<u64>(""), None);
+        assert_eq!(int::<u64>("-"), None);
+        assert_eq!(int::<u64>("1 "), None);
+        assert_eq!(int::<u64>("12345678x"), None);
+    }
+
+    #[test]
+    fn parses_long_integers() {
+        assert_eq!(
+            int::<u64>("12345678901234567"),
+            Some(12_345_678_901_234_567)
+        );
+        assert_eq!(int::<u64>("00000000000000000042"), Some(42));
+        assert_eq!(int::<u64>(u64::MAX.to_string()), Some(u64::MAX));
+        assert_eq!(int::<u64>("18446744073709551616"), None);
+        assert_eq!(int::<i64>(i64::MIN.to_string()), Some(i64::MIN));
+        assert_eq!(int::<i64>("9223372036854775808"), None);
+    }
+
+    #[test]
+    fn agrees_with_std_parse() {
+        for n in (0..100_000_000_000u64).step_by(7_919_111) {
+            let s = n.to_string();
+            assert_eq!(parse_digits(s.as_bytes()), s.parse().ok(), "{s}");
+        }
+
+        for s in ["1234567:", "/2345678", "12345678901:", "123456789 "] {
+            assert_eq!(parse_digits(s.as_bytes()), None, "{s}");
+        }
+    }
+
+    #[test]
+    fn finds_all_integers() {
+        assert_eq!(ints::<i32>("x=1, y=-2..3").collect::<Vec<_>>(), [1, -2, 3]);
+        assert_eq!(ints::<i32>("3-5,-7").collect::<Vec<_>>(), [3, 5, -7]);
+        assert_eq!(ints::<u32>("x=-1").collect::<Vec<_>>(), [1]);
+        assert_eq!(ints::<u8>("no numbers").count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 300 to fit in u8")]
+    fn panics_on_overflow_in_lists() {
+        let _ = ints::<u8>("1 300").count();
+    }
+
+    #[test]
+    fn parses_separated_integers() {
+        assert_eq!(pair::<u64>("11-22", "-"), Some((11, 22)));
+        assert_eq!(pair::<i64>("3 -> -4", "->"), Some((3, -4)));
+        assert_eq!(pair::<u64>("11", "-"), None);
+        assert_eq!(triple::<u32>("1,2,3", ","), Some((1, 2, 3)));
+        assert_eq!(triple::<u32>("1,2", ","), None);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let lf = "a\nb\n\nc\n\n\nd\n";
+        let crlf = "a\r\nb\r\n\r\nc\r\n\r\n\r\nd\r\n";
+        assert_eq!(sections(lf).collect::<Vec<_>>(), ["a\nb", "c", "d"]);
+        assert_eq!(sections(crlf).collect::<Vec<_>>(), ["a\r\nb", "c", "d"]);
+        assert_eq!(sections("\n\na").collect::<Vec<_>>(), ["a"]);
+        // a row of spaces belongs to its section.
+        let padded = "#.#\n   \n#.#\n\nx";
+        assert_eq!(sections(padded).collect::<Vec<_>>(), ["#.#\n   \n#.#", "x"]);
+        assert_eq!(sections("a\r\n  \r\n").collect::<Vec<_>>(), ["a\r\n  "]);
+        assert_eq!(sections(" a\n\n").collect::<Vec<_>>(), [" a"]);
+        assert_eq!(sections("").count(), 0);
+    }
+}
@@ -0,0 +1,365 @@
+//! Helpers for sets of 2D or 3D points: nearest neighbours, closest pairs and minimum spanning
+//! trees, without materialising all `n²` pairs of points.
+//!
+//! Distances are squared Euclidean distances, which compare the same as the actual distances but
+//! stay integers. They fit in a `u64` as long as coordinates differ by less than about `2³¹`.
+use std::{collections::BinaryHeap, ops::ControlFlow};
+
+use crate::disjoint_set::DisjointSet;
+
+/// A point with `D` coordinates, e.g. `[x, y, z]`.
+pub type Point<const D: usize> = [i64; D];
+
+/// The squared Euclidean distance between two points.
+#[must_use]
+pub fn distance_squared<const D: usize>(a: &Point<D>, b: &Point<D>) -> u64 {
+    a.iter().zip(b).map(|(a, b)| a.abs_diff(*b).pow(2)).sum()
+}
+
+/// A [k-d tree](https://en.wikipedia.org/wiki/K-d_tree) for nearest-neighbour queries.
+///
+/// Points are referred to by their index in the `Vec` the tree was built from.
+#[derive(Clone, Debug)]
+pub struct KdTree<const D: usize> {
+    points: Vec<Point<D>>,
+    /// Indices of the points, where every range is split at its middle element, alternating the
+    /// axis that is split along.
+    order: Vec<usize>,
+}
+
+impl<const D: usize> KdTree<D> {
+    #[must_use]
+    pub fn new(points: Vec<Point<D>>) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(&mut order, &points, 0);
+        Self { points, order }
+    }
+
+    #[must_use]
+    pub fn points(&self) -> &[Point<D>] {
+        &self.points
+    }
+
+    /// The index of the point closest to `query` and its distance.
+    #[must_use]
+    pub fn nearest(&self, query: &Point<D>) -> Option<(usize, u64)> {
+        self.k_nearest(query, 1).into_iter().next()
+    }
+
+    /// The indices of the `k` points closest to `query` and their distances, closest first.
+    /// Ties are broken by index.
+    #[must_use]
+    pub fn k_nearest(&self, query: &Point<D>, k: usize) -> Vec<(usize, u64)> {
+        let query = Query {
+            point: query,
+            from: 0,
+            min_index: 0,
+            k,
+        };
+        let mut best = BinaryHeap::with_capacity(k.min(self.points.len()).saturating_add(1));
+        self.search(&self.order, 0, &query, &mut best);
+
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|edge| (edge.to, edge.cost))
+            .collect()
+    }
+
+    /// Adds the edges to the points in `order` that are among the `k` cheapest to `best`.
+    fn search(
+        &self,
+        order: &[usize],
+        axis: usize,
+        query: &Query<D>,
+        best: &mut BinaryHeap<Edge<u64>>,
+    ) {
+        if order.is_empty() || query.k == 0 {
+            return;
+        }
+
+        let mid = order.len() / 2;
+        let to = order[mid];
+        let point = &self.points[to];
+
+        if to >= query.min_index {
+            best.push(Edge {
+                cost: distance_squared(point, query.point),
+                from: query.from,
+                to,
+            });
+            if best.len() > query.k {
+                best.pop();
+            }
+        }
+
+        let (near, far) = if query.point[axis] < point[axis] {
+            (&order[..mid], &order[mid + 1..])
+        } else {
+            (&order[mid + 1..], &order[..mid])
+        };
+
+        let next_axis = (axis + 1) % D;
+        self.search(near, next_axis, query, best);
+
+        // the far side can only contain closer points if the splitting plane is close enough.
+        let plane_distance = query.point[axis].abs_diff(point[axis]).pow(2);
+        if best.len() < query.k
+            || best
+                .peek()
+                .is_some_and(|worst| plane_distance <= worst.cost)
+        {
+            self.search(far, next_axis, query, best);
+        }
+    }
+}
+
+/// A search for the `k` closest points to `point`, recorded as edges from the node `from`.
+struct Query<'a, const D: usize> {
+    point: &'a Point<D>,
+    from: usize,
+    /// Points with a smaller index are skipped.
+    min_index: usize,
+    k: usize,
+}
+
+fn build<const D: usize>(order: &mut [usize], points: &[Point<D>], axis: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |&index| points[index][axis]);
+
+    let (left, right) = order.split_at_mut(mid);
+    build(left, points, (axis + 1) % D);
+    build(&mut right[1..], points, (axis + 1) % D);
+}
+
+/// An edge between the points or nodes `from` and `to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edge<C> {
+    pub cost: C,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The `k` pairs of points that are closest to each other, closest first, with `from < to`.
+/// Ties are broken by index.
+///
+/// Instead of comparing all pairs, this searches a [`KdTree`] for the neighbours of every point
+/// that are closer than the `k`-th closest pair found so far.
+#[must_use]
+pub fn closest_pairs<const D: usize>(points: &[Point<D>], k: usize) -> Vec<Edge<u64>> {
+    let tree = KdTree::new(points.to_vec());
+    let mut best = BinaryHeap::with_capacity(k.min(points.len()).saturating_add(1));
+
+    // the heap is shared between the searches, so every search only has to find pairs that are
+    // closer than the ones found so far.
+    for (from, point) in points.iter().enumerate() {
+        let query = Query {
+            point,
+            from,
+            min_index: from + 1,
+            k,
+        };
+        tree.search(&tree.order, 0, &query, &mut best);
+    }
+
+    best.into_sorted_vec()
+}
+
+/// Builds a minimum spanning forest of `n` nodes with [Kruskal's algorithm](https://en.wikipedia.org/wiki/Kruskal%27s_algorithm),
+/// returning the edges that were added, cheapest first.
+///
+/// `visit` is called for every added edge, along with the components connected so far. Return
+/// [`ControlFlow::Break`] from it to stop early, e.g. once everything is connected.
+pub fn kruskal<C: Ord + Copy>(
+    n: usize,
+    edges: impl IntoIterator<Item = Edge<C>>,
+    mut visit: impl FnMut(&Edge<C>, &DisjointSet) -> ControlFlow<()>,
+) -> Vec<Edge<C>> {
+    let mut edges: Vec<Edge<C>> = edges.into_iter().collect();
+    edges.sort_unstable();
+
+    let mut components = DisjointSet::new(n);
+    let mut tree = vec![];
+
+    for edge in edges {
+        if components.union(edge.from, edge.to) {
+            tree.push(edge);
+            if visit(&edge, &components).is_break() {
+                break;
+            }
+        }
+    }
+
+    tree
+}
+
+/// Builds a minimum spanning tree of the complete graph of `n` nodes with [Prim's algorithm](https://en.wikipedia.org/wiki/Prim%27s_algorithm),
+/// returning its edges in the order they were added.
+///
+/// Takes `O(n²)` time but no memory for the edges, which suits dense graphs like all pairs of
+/// points. `visit` is called for every added edge, return [`ControlFlow::Break`] to stop early.
+pub fn prim<C: Ord + Copy>(
+    n: usize,
+    mut cost: impl FnMut(usize, usize) -> C,
+    mut visit: impl FnMut(&Edge<C>) -> ControlFlow<()>,
+) -> Vec<Edge<C>> {
+    let mut tree = Vec::with_capacity(n.saturating_sub(1));
+    // the cheapest edge from the tree to every node that is not part of it yet.
+    let mut cheapest: Vec<Option<Edge<C>>> = vec![None; n];
+    let mut in_tree = vec![false; n];
+    let mut current = 0;
+
+    for _ in 1..n {
+        in_tree[current] = true;
+
+        for to in (0..n).filter(|&to| !in_tree[to]) {
+            let edge = Edge {
+                cost: cost(current, to),
+                from: current,
+                to,
+            };
+            if cheapest[to].is_none_or(|best| edge.cost < best.cost) {
+                cheapest[to] = Some(edge);
+            }
+        }
+
+        let Some(edge) = (0..n)
+            .filter(|&to| !in_tree[to])
+            .filter_map(|to| cheapest[to])
+            .min_by_key(|edge| edge.cost)
+        else {
+            break;
+        };
+
+        tree.push(edge);
+        current = edge.to;
+        if visit(&edge).is_break() {
+            break;
+        }
+    }
+
+    tree
+}
+
+/// The minimum spanning tree of a set of points, using [`prim`].
+#[must_use]
+pub fn euclidean_mst<const D: usize>(points: &[Point<D>]) -> Vec<Edge<u64>> {
+    prim(
+        points.len(),
+        |a, b| distance_squared(&points[a], &points[b]),
+        |_| ControlFlow::Continue(()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use super::{Edge, KdTree, Point, closest_pairs, distance_squared, euclidean_mst, kruskal};
+
+    /// Pseudo-random points, some of them duplicates.
+    fn random_points<const D: usize>(n: usize, seed: u64) -> Vec<Point<D>> {
+        let mut state = seed;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 33) as i64 % 1000 - 500
+        };
+        let mut points: Vec<Point<D>> = (0..n).map(|_| std::array::from_fn(|_| next())).collect();
+        points[n - 1] = points[0];
+        points
+    }
+
+    fn brute_force_pairs<const D: usize>(points: &[Point<D>], k: usize) -> Vec<Edge<u64>> {
+        let mut pairs = vec![];
+        for from in 0..points.len() {
+            for to in from + 1..points.len() {
+                let cost = distance_squared(&points[from], &points[to]);
+                pairs.push(Edge { cost, from, to });
+            }
+        }
+        pairs.sort_unstable();
+        pairs.truncate(k);
+        pairs
+    }
+
+    #[test]
+    fn measures_distances() {
+        assert_eq!(distance_squared(&[0, 0], &[3, -4]), 25);
+        assert_eq!(distance_squared(&[1, 2, 3], &[1, 2, 3]), 0);
+    }
+
+    #[test]
+    fn finds_nearest_neighbours() {
+        let points = random_points::<3>(300, 1);
+        let tree = KdTree::new(points.clone());
+
+        for query in random_points::<3>(50, 2) {
+            let mut expected: Vec<(usize, u64)> = points
+                .iter()
+                .enumerate()
+                .map(|(index, point)| (index, distance_squared(point, &query)))
+                .collect();
+            expected.sort_unstable_by_key(|&(index, distance)| (distance, index));
+            expected.truncate(5);
+
+            assert_eq!(tree.k_nearest(&query, 5), expected);
+            assert_eq!(tree.nearest(&query), Some(expected[0]));
+        }
+
+        let all = tree.k_nearest(&[0, 0, 0], usize::MAX);
+        assert_eq!(all.len(), points.len());
+        assert!(all.is_sorted_by_key(|&(index, distance)| (distance, index)));
+
+        assert_eq!(KdTree::<2>::new(vec![]).nearest(&[0, 0]), None);
+    }
+
+    #[test]
+    fn finds_closest_pairs() {
+        let points = random_points::<3>(200, 3);
+        assert_eq!(closest_pairs(&points, 50), brute_force_pairs(&points, 50));
+
+        let points = random_points::<2>(100, 4);
+        assert_eq!(closest_pairs(&points, 10), brute_force_pairs(&points, 10));
+        assert_eq!(
+            closest_pairs(&points, usize::MAX),
+            brute_force_pairs(&points, usize::MAX)
+        );
+    }
+
+    #[test]
+    fn builds_minimum_spanning_trees() {
+        let points = random_points::<2>(100, 5);
+        let all_pairs = brute_force_pairs(&points, usize::MAX);
+
+        let expected = kruskal(points.len(), all_pairs, |_, _| ControlFlow::Continue(()));
+        let tree = euclidean_mst(&points);
+        assert_eq!(tree.len(), points.len() - 1);
+        assert_eq!(
+            tree.iter().map(|edge| edge.cost).sum::<u64>(),
+            expected.iter().map(|edge| edge.cost).sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn stops_kruskal_early() {
+        let edges = [(1, 0, 1), (2, 1, 2), (3, 0, 2), (4, 2, 3), (5, 3, 4)]
+            .map(|(cost, from, to)| Edge { cost, from, to });
+
+        let tree = kruskal(5, edges, |_, components| {
+            if components.component_count() == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(
+            tree.iter().map(|edge| edge.cost).collect::<Vec<_>>(),
+            [1, 2, 4]
+        );
+    }
+}
@@ -1,10 +1,14 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io,
     process::{Command, Output, Stdio},
 };
 
 use crate::template::Day;
+use crate::template::output::{self, Event, Marker};
+use crate::template::puzzle::get_puzzle_path;
+use crate::template::workspace::Workspace;
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -66,9 +70,61 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     );
 
     let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+
+    if output::is_json() {
+        Event::new("downloaded")
+            .with("day", day.to_string())
+            .with("input", input_path)
+            .with("puzzle", puzzle_path)
+            .emit();
+    } else {
+        println!("---");
+        println!(
+            "{} Successfully wrote input to \"{}\".",
+            Marker::Tree,
+            &input_path
+        );
+        println!(
+            "{} Successfully wrote puzzle to \"{}\".",
+            Marker::Tree,
+            &puzzle_path
+        );
+    }
+
+    Ok(output)
+}
+
+/// Downloads only the puzzle description, e.g. to refresh it once part two is unlocked.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(day);
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path.to_string(),
+        ],
+        day,
+    );
+
+    let output = call_aoc_cli(&args)?;
+
+    if output::is_json() {
+        Event::new("downloaded")
+            .with("day", day.to_string())
+            .with("puzzle", puzzle_path)
+            .emit();
+    } else {
+        println!("---");
+        println!(
+            "{} Successfully wrote puzzle to \"{}\".",
+            Marker::Tree,
+            &puzzle_path
+        );
+    }
+
     Ok(output)
 }
 
@@ -77,18 +133,49 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let output = Command::new("aoc")
+        .args(&args)
+        .current_dir(Workspace::current().root())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    // mirror the response to stdout, it is needed to tell whether the answer was accepted.
+    let response = String::from_utf8_lossy(&output.stdout);
+    if output::is_json() {
+        eprint!("{response}");
+    } else {
+        print!("{response}");
+    }
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Returns true if the output of a `submit` call reports a correct answer.
+#[must_use]
+pub fn is_answer_accepted(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Returns true if the output of a `submit` call reports a wrong answer. Submissions can also be
+/// neither accepted nor rejected, e.g. if they were sent too soon after the previous one.
+#[must_use]
+pub fn is_answer_rejected(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's not the right answer")
 }
 
-fn get_year() -> Option<u16> {
+fn get_input_path(day: Day) -> String {
+    format!("data/inputs/{day}.txt")
+}
+
+/// Returns the year configured via the `AOC_YEAR` environment variable.
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -110,9 +197,18 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
+    // keep stdout free for events in JSON mode.
+    let stdout = if output::is_json() {
+        Stdio::from(io::stderr())
+    } else {
+        Stdio::inherit()
+    };
+
+    // paths passed to aoc-cli are relative to the project root.
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .current_dir(Workspace::current().root())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
@@ -3,4 +3,6 @@ pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod unscaffold;
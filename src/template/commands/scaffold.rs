@@ -1,79 +1,659 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{fs, io};
 
-use crate::template::Day;
+use crate::template::output::{self, Event, Marker};
+use crate::template::{Day, Error, aoc_cli, puzzle, workspace};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+const DEFAULT_TEMPLATE_NAME: &str = "default";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
+/// Fallback for the default template if the `templates` directory is missing.
+const DEFAULT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.rs.tmpl"
+));
+
+/// Values that are substituted for the placeholders of a module template.
+pub struct TemplateContext {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub title: Option<String>,
+    pub example_answers: [Option<String>; 2],
+}
+
+impl TemplateContext {
+    /// Creates a context for a day, reading the title and example answers from its puzzle
+    /// description if present.
+    pub fn new(day: Day) -> Self {
+        let description = puzzle::read_description(day).unwrap_or_default();
+        let title = puzzle::parse_title(&description).map(String::from);
+        let (part_one, part_two) = puzzle::split_parts(&description);
+
+        Self {
+            day,
+            year: aoc_cli::get_year(),
+            title,
+            example_answers: [
+                puzzle::parse_example_answer(part_one),
+                part_two.and_then(puzzle::parse_example_answer),
+            ],
+        }
+    }
+
+    /// The answer type is inferred from the example answers of both parts, as both parts share
+    /// it, and defaults to `u64`.
+    fn answer_type(&self) -> &'static str {
+        let mut answers = self.example_answers.iter().flatten();
+
+        if answers.clone().all(|answer| answer.parse::<u64>().is_ok()) {
+            "u64"
+        } else if answers.all(|answer| answer.parse::<i64>().is_ok()) {
+            "i64"
+        } else {
+            "String"
+        }
+    }
+
+    /// Substitutes all placeholders in a template. Lines with `%YEAR%` are left out if no year
+    /// is configured, as they would render a broken link.
+    pub fn render(&self, template: &str) -> String {
+        let day = self.day.into_inner();
+
+        let template = match self.year {
+            Some(_) => template.to_string(),
+            None => template
+                .split_inclusive('\n')
+                .filter(|line| !line.contains("%YEAR%"))
+                .collect(),
+        };
+
+        let module = template
+            .replace("%DAY_NUMBER%", &day.to_string())
+            .replace(
+                "%YEAR%",
+                &self.year.map(|year| year.to_string()).unwrap_or_default(),
+            )
+            .replace(
+                "%PUZZLE_TITLE%",
+                &self.title.clone().unwrap_or_else(|| format!("Day {day}")),
+            )
+            .replace("%ANSWER_TYPE%", self.answer_type());
+
+        // the examples are formatted for the type the template declares, which may be fixed.
+        let example = |part: usize, function: &str| {
+            let answer_type = declared_answer_type(&module, function).unwrap_or(self.answer_type());
+            self.example_answers[part]
+                .as_deref()
+                .and_then(|answer| format_answer(answer, answer_type))
+                .unwrap_or_else(|| "None".into())
+        };
+
+        module
+            .replace("%EXAMPLE_PART_ONE%", &example(0, "part_one"))
+            .replace("%EXAMPLE_PART_TWO%", &example(1, "part_two"))
+    }
+}
+
+/// Formats an expected answer as the `Some` literal a solution with the given answer type
+/// returns, or `None` if the answer is not a value of that type.
+fn format_answer(answer: &str, answer_type: &str) -> Option<String> {
+    if answer_type == "String" {
+        Some(format!("Some({answer:?}.to_string())"))
+    } else if parses_as_integer(answer, answer_type) {
+        Some(format!("Some({answer})"))
     } else {
-        file.create_new(true);
+        None
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Whether `answer` is a value of the integer type `answer_type`, e.g. `-3` of `i64`.
+fn parses_as_integer(answer: &str, answer_type: &str) -> bool {
+    match answer_type {
+        "u8" => answer.parse::<u8>().is_ok(),
+        "u16" => answer.parse::<u16>().is_ok(),
+        "u32" => answer.parse::<u32>().is_ok(),
+        "u64" => answer.parse::<u64>().is_ok(),
+        "u128" => answer.parse::<u128>().is_ok(),
+        "usize" => answer.parse::<usize>().is_ok(),
+        "i8" => answer.parse::<i8>().is_ok(),
+        "i16" => answer.parse::<i16>().is_ok(),
+        "i32" => answer.parse::<i32>().is_ok(),
+        "i64" => answer.parse::<i64>().is_ok(),
+        "i128" => answer.parse::<i128>().is_ok(),
+        "isize" => answer.parse::<isize>().is_ok(),
+        _ => false,
+    }
+}
+
+/// The answer type a solution module declares for a part, e.g. `u64` for `-> Option<u64>`.
+fn declared_answer_type<'a>(module: &'a str, function: &str) -> Option<&'a str> {
+    const RETURN_TYPE: &str = "-> Option<";
+
+    let start = module.find(&format!("fn {function}("))?;
+    let signature = &module[start..start + module[start..].find('{')?];
+    let answer_type = &signature[signature.find(RETURN_TYPE)? + RETURN_TYPE.len()..];
+    answer_type.trim_end().strip_suffix('>')
+}
+
+/// Replaces the first occurrence of `from` inside a test function of a solution with `to`.
+/// Returns `None` if the test does not exist or does not contain `from`.
+fn replace_in_test(source: &str, test_name: &str, from: &str, to: &str) -> Option<String> {
+    let start = source.find(&format!("fn {test_name}()"))?;
+    let offset = source[start..].find(from)?;
+
+    // the match has to belong to this test, not to one of the following functions.
+    if source[start + 1..start + offset].contains("fn ") {
+        return None;
+    }
+
+    let mut source = source.to_string();
+    let position = start + offset;
+    source.replace_range(position..position + from.len(), to);
+    Some(source)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
+/// Replaces the `None` expectation of a test in a solution with the expected answer.
+/// Returns `None` if the test does not exist or does not expect `None` anymore.
+fn set_expected_answer(source: &str, test_name: &str, expected: &str) -> Option<String> {
+    replace_in_test(
+        source,
+        test_name,
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, {expected});"),
+    )
+}
+
+fn is_empty_file(path: &str) -> bool {
+    fs::metadata(workspace::path(path)).is_ok_and(|m| m.len() == 0)
+}
+
+fn is_missing_or_empty_file(path: &str) -> bool {
+    fs::metadata(workspace::path(path)).map_or(true, |m| m.len() == 0)
+}
+
+/// Fills in the example input and the expected example answers from a downloaded puzzle
+/// description. Example files are only written if they are empty and only tests that still
+/// expect `None` are updated.
+pub fn populate_examples(day: Day) -> Result<(), Error> {
+    let Some(description) = puzzle::read_description(day) else {
+        return Ok(());
+    };
+
+    let (part_one, part_two) = puzzle::split_parts(&description);
+
     let example_path = format!("data/examples/{day}.txt");
+    if let Some(example) = puzzle::parse_examples(part_one).first()
+        && is_empty_file(&example_path)
+    {
+        fs::write(workspace::path(&example_path), example)
+            .map_err(Error::io("Failed to write example file"))?;
+        print_file_event(
+            "updated",
+            "example",
+            "Copied example from puzzle description to",
+            &example_path,
+        );
+    }
+
     let module_path = format!("src/bin/{day}.rs");
+    let Ok(mut module) = fs::read_to_string(workspace::path(&module_path)) else {
+        return Ok(());
+    };
+
+    let answers = [Some(part_one), part_two].map(|s| s.and_then(puzzle::parse_example_answer));
+    let mut is_changed = false;
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    for (part, answer) in ["part_one", "part_two"].into_iter().zip(answers) {
+        let answer_type = declared_answer_type(&module, part).unwrap_or("u64");
+        if let Some(answer) = answer
+            && let Some(expected) = format_answer(&answer, answer_type)
+            && let Some(updated) = set_expected_answer(&module, &format!("test_{part}"), &expected)
+        {
+            module = updated;
+            is_changed = true;
         }
-    };
+    }
+
+    if is_changed {
+        fs::write(workspace::path(&module_path), module)
+            .map_err(Error::io("Failed to update module file"))?;
+        print_file_event(
+            "updated",
+            "module",
+            "Filled in expected example answers in",
+            &module_path,
+        );
+    }
+
+    Ok(())
+}
+
+#[must_use]
+pub fn get_template_path(name: &str) -> String {
+    format!("templates/{name}.rs.tmpl")
+}
+
+/// Reads a module template from the `templates` directory.
+fn read_template(name: &str) -> Result<String, Error> {
+    let path = get_template_path(name);
+
+    if name == DEFAULT_TEMPLATE_NAME && !workspace::path(&path).exists() {
+        return Ok(DEFAULT_TEMPLATE.into());
+    }
+
+    fs::read_to_string(workspace::path(&path)).map_err(|e| {
+        let e = if e.kind() == io::ErrorKind::NotFound {
+            io::Error::new(e.kind(), format!("no template named \"{name}\""))
+        } else {
+            e
+        };
+        Error::Io(format!("Failed to read template \"{path}\""), e)
+    })
+}
+
+/// What scaffolding does with one of the files of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileAction {
+    /// The file is missing or empty and is (re)created.
+    Create,
+    /// The file has contents, which are kept.
+    Skip,
+    /// The file has contents, which are backed up before the file is recreated.
+    Overwrite,
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+impl FileAction {
+    fn plan(path: &str, overwrite: bool) -> Self {
+        if is_missing_or_empty_file(path) {
+            FileAction::Create
+        } else if overwrite {
+            FileAction::Overwrite
+        } else {
+            FileAction::Skip
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            FileAction::Create => "create",
+            FileAction::Skip => "skip",
+            FileAction::Overwrite => "overwrite",
         }
     }
+}
+
+/// A file created by `scaffold`, along with what should happen to it.
+struct ScaffoldFile<'a> {
+    kind: &'static str,
+    path: String,
+    contents: &'a str,
+    action: FileAction,
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+impl ScaffoldFile<'_> {
+    fn print_plan(&self) {
+        if output::is_json() {
+            Event::new("planned")
+                .with("kind", self.kind.to_string())
+                .with("path", self.path.clone())
+                .with("action", self.action.as_str().to_string())
+                .emit();
+        } else {
+            let message = match self.action {
+                FileAction::Create => format!("Would create {} file", self.kind),
+                FileAction::Skip => format!("Would skip existing {} file", self.kind),
+                FileAction::Overwrite => {
+                    format!("Would back up and overwrite existing {} file", self.kind)
+                }
+            };
+            println!("{message} \"{}\"", self.path);
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    }
+
+    fn apply(&self) -> Result<(), Error> {
+        if self.action == FileAction::Skip {
+            if output::is_json() {
+                Event::new("skipped")
+                    .with("kind", self.kind.to_string())
+                    .with("path", self.path.clone())
+                    .emit();
+            } else {
+                println!(
+                    "Skipped existing {} file \"{}\", pass --overwrite to replace it",
+                    self.kind, self.path
+                );
+            }
+            return Ok(());
+        }
+
+        if self.action == FileAction::Overwrite {
+            let backup_path = get_backup_path(&self.path);
+            fs::copy(workspace::path(&self.path), workspace::path(&backup_path))
+                .map_err(Error::io(&format!("Failed to back up {} file", self.kind)))?;
+
+            if output::is_json() {
+                Event::new("backed_up")
+                    .with("kind", self.kind.to_string())
+                    .with("path", self.path.clone())
+                    .with("backup", backup_path)
+                    .emit();
+            } else {
+                println!("Backed up \"{}\" to \"{backup_path}\"", self.path);
+            }
         }
+
+        fs::write(workspace::path(&self.path), self.contents)
+            .map_err(Error::io(&format!("Failed to create {} file", self.kind)))?;
+
+        let message = if self.contents.is_empty() {
+            format!("Created empty {} file", self.kind)
+        } else {
+            format!("Created {} file", self.kind)
+        };
+        print_created(self.kind, &message, &self.path);
+        Ok(())
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+/// Returns a path next to `path` that does not exist yet, so that backups never replace
+/// each other.
+fn get_backup_path(path: &str) -> String {
+    let mut backup_path = format!("{path}.bak");
+    let mut i = 1;
+    while workspace::path(&backup_path).exists() {
+        backup_path = format!("{path}.bak.{i}");
+        i += 1;
+    }
+    backup_path
+}
+
+fn print_created(kind: &str, message: &str, path: &str) {
+    print_file_event("created", kind, message, path);
+}
+
+fn print_file_event(event: &str, kind: &str, message: &str, path: &str) {
+    if output::is_json() {
+        Event::new(event)
+            .with("kind", kind.to_string())
+            .with("path", path.to_string())
+            .emit();
+    } else {
+        println!("{message} \"{path}\"");
+    }
+}
+
+/// Creates the module, input and example files of a day.
+///
+/// Files that already have contents are skipped, or backed up and replaced if `overwrite` is
+/// set. With `dry_run`, only the planned actions are printed.
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    dry_run: bool,
+    template: Option<&str>,
+) -> Result<(), Error> {
+    let template = read_template(template.unwrap_or(DEFAULT_TEMPLATE_NAME))?;
+    let contents = TemplateContext::new(day).render(&template);
+
+    let files = [
+        ("module", format!("src/bin/{day}.rs"), contents.as_str()),
+        ("input", format!("data/inputs/{day}.txt"), ""),
+        ("example", format!("data/examples/{day}.txt"), ""),
+    ]
+    .map(|(kind, path, contents)| ScaffoldFile {
+        action: FileAction::plan(&path, overwrite),
+        kind,
+        path,
+        contents,
+    });
+
+    if dry_run {
+        files.iter().for_each(ScaffoldFile::print_plan);
+        return Ok(());
+    }
+
+    for file in &files {
+        file.apply()?;
+    }
+
+    populate_examples(day)?;
+
+    if output::is_json() {
+        Event::new("scaffolded").with("day", day.to_string()).emit();
+    } else {
+        println!("---");
+        println!(
+            "{} Type `cargo solve {day}` to run your solution.",
+            Marker::Tree
+        );
+    }
+
+    Ok(())
+}
+
+/// Refreshes the puzzle description once part one has been solved and scaffolds part two:
+/// a separate example file is added if the example of part two differs and the part two test
+/// is updated to use it and to expect the example answer.
+pub fn handle_part_two(day: Day) -> Result<(), Error> {
+    aoc_cli::check()?;
+    aoc_cli::download_puzzle(day)?;
+    populate_examples(day)?;
+    populate_part_two(day)
+}
+
+fn populate_part_two(day: Day) -> Result<(), Error> {
+    let description = puzzle::read_description(day).unwrap_or_default();
+
+    let (_, Some(part_two)) = puzzle::split_parts(&description) else {
+        if output::is_json() {
+            Event::new("locked").with("day", day.to_string()).emit();
+        } else {
+            println!("Part two of day {day} has not been unlocked yet.");
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        return Ok(());
+    };
+
+    let example_path = format!("data/examples/{day}.txt");
+    let part_example_path = format!("data/examples/{day}-2.txt");
+    let example = fs::read_to_string(workspace::path(&example_path)).unwrap_or_default();
+
+    let part_example = puzzle::parse_examples(part_two)
+        .into_iter()
+        .next()
+        .filter(|part_example| part_example.trim_end() != example.trim_end());
+
+    if let Some(part_example) = &part_example
+        && is_missing_or_empty_file(&part_example_path)
+    {
+        fs::write(workspace::path(&part_example_path), part_example)
+            .map_err(Error::io("Failed to write example file"))?;
+        print_created(
+            "example",
+            "Copied example of part two to",
+            &part_example_path,
+        );
+    }
+
+    let module_path = format!("src/bin/{day}.rs");
+    let Ok(mut module) = fs::read_to_string(workspace::path(&module_path)) else {
+        return Ok(());
+    };
+
+    let mut is_changed = false;
+
+    if part_example.is_some()
+        && let Some(updated) = replace_in_test(
+            &module,
+            "test_part_two",
+            "read_file(\"examples\", DAY)",
+            "read_file_part(\"examples\", DAY, 2)",
+        )
+    {
+        module = updated;
+        is_changed = true;
+    }
+
+    let answer_type = declared_answer_type(&module, "part_two").unwrap_or("u64");
+    if let Some(answer) = puzzle::parse_example_answer(part_two)
+        && let Some(expected) = format_answer(&answer, answer_type)
+        && let Some(updated) = set_expected_answer(&module, "test_part_two", &expected)
+    {
+        module = updated;
+        is_changed = true;
+    }
+
+    if is_changed {
+        fs::write(workspace::path(&module_path), module)
+            .map_err(Error::io("Failed to update module file"))?;
+        print_file_event(
+            "updated",
+            "module",
+            "Updated part two test in",
+            &module_path,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEFAULT_TEMPLATE, TemplateContext, declared_answer_type, replace_in_test,
+        set_expected_answer,
+    };
+    use crate::day;
+
+    fn context_for_day_one() -> TemplateContext {
+        TemplateContext {
+            day: day!(1),
+            year: None,
+            title: None,
+            example_answers: [None, None],
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn renders_placeholders() {
+        let context = TemplateContext {
+            day: day!(5),
+            year: Some(2025),
+            title: Some("Day 5: Cafeteria".into()),
+            example_answers: [Some("3".into()), None],
+        };
+
+        let module = context.render(DEFAULT_TEMPLATE);
+        assert!(
+            module.starts_with("// Day 5: Cafeteria\n// https://adventofcode.com/2025/day/5\n")
+        );
+        assert!(module.contains("advent_of_code::solution!(5);"));
+        assert!(module.contains("-> Option<u64>"));
+        assert!(module.contains("assert_eq!(result, Some(3));"));
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(!module.contains('%'));
+    }
+
+    #[test]
+    fn leaves_out_the_year_if_it_is_not_set() {
+        let module = context_for_day_one().render(DEFAULT_TEMPLATE);
+        assert!(module.starts_with("// Day 1\nadvent_of_code::solution!(1);\n"));
+        assert!(!module.contains("adventofcode.com"));
+    }
+
+    #[test]
+    fn sets_expected_answers() {
+        let module = context_for_day_one().render(DEFAULT_TEMPLATE);
+
+        let module = set_expected_answer(&module, "test_part_two", "Some(281)").unwrap();
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(module.contains("assert_eq!(result, Some(281));"));
+        assert_eq!(
+            set_expected_answer(&module, "test_part_two", "Some(1)"),
+            None
+        );
+
+        let module = set_expected_answer(&module, "test_part_one", "Some(142)").unwrap();
+        assert!(!module.contains("assert_eq!(result, None);"));
+        assert!(module.find("Some(142)").unwrap() < module.find("Some(281)").unwrap());
+    }
+
+    #[test]
+    fn replaces_in_tests() {
+        let module = context_for_day_one().render(DEFAULT_TEMPLATE);
+        let module = replace_in_test(
+            &module,
+            "test_part_two",
+            "read_file(\"examples\", DAY)",
+            "read_file_part(\"examples\", DAY, 2)",
+        )
+        .unwrap();
+
+        let part_one = module.find("fn test_part_one").unwrap();
+        let part_two = module.find("fn test_part_two").unwrap();
+        assert!(module[part_one..part_two].contains("read_file(\"examples\", DAY)"));
+        assert!(module[part_two..].contains("read_file_part(\"examples\", DAY, 2)"));
+        assert_eq!(
+            replace_in_test(&module, "test_part_three", "DAY", "1"),
+            None
+        );
+    }
+
+    #[test]
+    fn infers_answer_types() {
+        let mut context = TemplateContext {
+            day: day!(1),
+            year: None,
+            title: None,
+            example_answers: [Some("-3".into()), Some("abc".into())],
+        };
+
+        let module = context.render("%ANSWER_TYPE% %EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO%");
+        assert_eq!(
+            module,
+            "String Some(\"-3\".to_string()) Some(\"abc\".to_string())"
+        );
+
+        context.example_answers = [Some("-3".into()), Some("4".into())];
+        let module = context.render("%ANSWER_TYPE% %EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO%");
+        assert_eq!(module, "i64 Some(-3) Some(4)");
+
+        context.example_answers = [None, Some("4".into())];
+        assert_eq!(context.render("%ANSWER_TYPE%"), "u64");
+    }
+
+    #[test]
+    fn formats_examples_for_declared_types() {
+        const I64_TEMPLATE: &str = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/templates/i64.rs.tmpl"
+        ));
+
+        let mut context = TemplateContext {
+            day: day!(1),
+            year: None,
+            title: None,
+            example_answers: [Some("abc".into()), Some("-3".into())],
+        };
+
+        let module = context.render(I64_TEMPLATE);
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(module.contains("assert_eq!(result, Some(-3));"));
+
+        let u64_template = I64_TEMPLATE.replace("i64", "u64");
+        context.example_answers = [Some("-3".into()), Some("4".into())];
+        let module = context.render(&u64_template);
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(module.contains("assert_eq!(result, Some(4));"));
+    }
+
+    #[test]
+    fn reads_declared_answer_types() {
+        let module = context_for_day_one().render(DEFAULT_TEMPLATE);
+        assert_eq!(declared_answer_type(&module, "part_one"), Some("u64"));
+
+        let module = module.replace(
+            "part_two(input: &str) -> Option<u64>",
+            "part_two(input: &str) -> Option<Vec<u8>>",
+        );
+        assert_eq!(declared_answer_type(&module, "part_two"), Some("Vec<u8>"));
+        assert_eq!(declared_answer_type(&module, "part_three"), None);
+    }
 }
@@ -1,8 +1,15 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::workspace::Workspace;
+use crate::template::{Day, Error, output};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    record: bool,
+) -> Result<(), Error> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -17,18 +24,34 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.push(output::color_arg());
+
+    if output::is_json() {
+        cmd_args.push("--json".to_string());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if record {
+        cmd_args.push("--record".to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .current_dir(Workspace::current().root())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
-        .unwrap();
+        .map_err(Error::Command)?;
+
+    let status = cmd.wait().map_err(Error::Command)?;
 
-    cmd.wait().unwrap();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Solution(status))
+    }
 }
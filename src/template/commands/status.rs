@@ -0,0 +1,222 @@
+use std::{collections::HashMap, fs};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::examples;
+use crate::template::output::{self, Event, Marker};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{Day, Error, all_days, workspace};
+
+/// Progress of a single day, as far as it can be inferred from the file system.
+struct DayStatus {
+    day: Day,
+    has_bin: bool,
+    has_input: bool,
+    has_example: bool,
+    has_puzzle: bool,
+    part_two_stubbed: bool,
+    timings: [Option<String>; 2],
+    answers: [Option<String>; 2],
+}
+
+impl DayStatus {
+    fn collect(day: Day, timings: &Timings, answers: &Answers) -> Self {
+        let source = fs::read_to_string(workspace::path(get_path_for_bin(day))).ok();
+        let timing = timings.data.iter().find(|t| t.day == day);
+
+        Self {
+            day,
+            has_bin: source.is_some(),
+            has_input: is_non_empty(&format!("data/inputs/{day}.txt")),
+            has_example: is_non_empty(&format!("data/examples/{day}.txt"))
+                || examples::get_examples_dir(day).is_dir(),
+            has_puzzle: is_non_empty(&format!("data/puzzles/{day}.md")),
+            part_two_stubbed: source.as_deref().is_some_and(is_part_two_stubbed),
+            timings: [
+                timing.and_then(|t| t.part_1.clone()),
+                timing.and_then(|t| t.part_2.clone()),
+            ],
+            answers: [
+                answers.get(day, 1).map(String::from),
+                answers.get(day, 2).map(String::from),
+            ],
+        }
+    }
+
+    /// Days without any artifacts are left out of the report.
+    fn is_started(&self) -> bool {
+        self.has_bin
+            || self.has_input
+            || self.has_example
+            || self.has_puzzle
+            || self.timings.iter().any(Option::is_some)
+            || self.answers.iter().any(Option::is_some)
+    }
+
+    fn stars(&self) -> usize {
+        self.answers.iter().filter(|a| a.is_some()).count()
+    }
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let parts = |values: &[Option<String>; 2]| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            for (i, value) in values.iter().enumerate() {
+                map.insert(
+                    format!("part_{}", i + 1),
+                    value.clone().map_or(JsonValue::Null, JsonValue::String),
+                );
+            }
+            JsonValue::Object(map)
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("bin".into(), JsonValue::Boolean(value.has_bin));
+        map.insert("input".into(), JsonValue::Boolean(value.has_input));
+        map.insert("example".into(), JsonValue::Boolean(value.has_example));
+        map.insert("puzzle".into(), JsonValue::Boolean(value.has_puzzle));
+        map.insert(
+            "part_two_stubbed".into(),
+            JsonValue::Boolean(value.part_two_stubbed),
+        );
+        map.insert("timings".into(), parts(&value.timings));
+        map.insert("answers".into(), parts(&value.answers));
+        JsonValue::Object(map)
+    }
+}
+
+fn is_non_empty(path: &str) -> bool {
+    fs::metadata(workspace::path(path)).is_ok_and(|m| m.len() > 0)
+}
+
+/// Returns true if `part_two` in a solution source still is the template stub that returns `None`.
+fn is_part_two_stubbed(source: &str) -> bool {
+    let Some(start) = source.find("fn part_two") else {
+        return false;
+    };
+
+    let Some(offset) = source[start..].find('{') else {
+        return false;
+    };
+
+    let body_start = start + offset + 1;
+    let mut depth = 1;
+
+    for (i, c) in source[body_start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return source[body_start..body_start + i].trim() == "None";
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+fn mark(value: bool) -> String {
+    if value {
+        Marker::Check.to_string()
+    } else {
+        "-".into()
+    }
+}
+
+fn mark_parts(values: &[Option<String>; 2]) -> String {
+    format!(
+        "{} {}",
+        if values[0].is_some() { "1" } else { "-" },
+        if values[1].is_some() { "2" } else { "-" }
+    )
+}
+
+fn print_report(days: &[DayStatus]) {
+    if days.is_empty() {
+        println!("No days have been scaffolded yet.");
+        return;
+    }
+
+    println!(
+        "{}",
+        output::bold(format!(
+            "{:<5}{:<5}{:<7}{:<9}{:<8}{:<8}{:<7}{:<7}",
+            "Day", "Bin", "Input", "Example", "Puzzle", "Part 2", "Timed", "Solved"
+        ))
+    );
+
+    for status in days {
+        let part_two = if !status.has_bin {
+            "-".into()
+        } else if status.part_two_stubbed {
+            "stub".into()
+        } else {
+            Marker::Check.to_string()
+        };
+
+        println!(
+            "{:<5}{:<5}{:<7}{:<9}{:<8}{:<8}{:<7}{:<7}",
+            status.day.to_string(),
+            mark(status.has_bin),
+            mark(status.has_input),
+            mark(status.has_example),
+            mark(status.has_puzzle),
+            part_two,
+            mark_parts(&status.timings),
+            mark_parts(&status.answers),
+        );
+    }
+
+    let stars: usize = days.iter().map(DayStatus::stars).sum();
+    println!("---");
+    println!("{} day(s) started, {stars} star(s) collected.", days.len());
+}
+
+pub fn handle() -> Result<(), Error> {
+    let timings = Timings::read_from_file();
+    let answers = Answers::read_from_file();
+
+    let days: Vec<DayStatus> = all_days()
+        .map(|day| DayStatus::collect(day, &timings, &answers))
+        .filter(DayStatus::is_started)
+        .collect();
+
+    if output::is_json() {
+        Event::new("status")
+            .with("days", days.iter().map(JsonValue::from).collect::<Vec<_>>())
+            .emit();
+    } else {
+        print_report(&days);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_part_two_stubbed;
+
+    #[test]
+    fn detects_stubbed_part_two() {
+        let source = "pub fn part_two(input: &str) -> Option<u64> {\n    None\n}\n";
+        assert!(is_part_two_stubbed(source));
+    }
+
+    #[test]
+    fn detects_implemented_part_two() {
+        let source = "pub fn part_two(input: &str) -> Option<u64> {\n    if true { return None; }\n    Some(1)\n}\n";
+        assert!(!is_part_two_stubbed(source));
+    }
+
+    #[test]
+    fn handles_missing_part_two() {
+        assert!(!is_part_two_stubbed("pub fn part_one() {}"));
+    }
+}
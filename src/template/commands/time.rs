@@ -1,10 +1,11 @@
 use std::collections::HashSet;
 
+use crate::template::output::{self, Event};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, Error, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(day: Option<Day>, run_all: bool, store: bool) -> Result<(), Error> {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,20 +22,28 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, false)?.unwrap_or_default();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file()
+            .map_err(Error::io("failed to store timings"))?;
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+        if !output::is_json() {
+            println!();
+        }
+
+        readme_benchmarks::update(merged_timings)?;
+
+        if output::is_json() {
+            Event::new("stored")
+                .with("path", "README.md".to_string())
+                .emit();
+        } else {
+            println!("Stored updated benchmarks.");
         }
     }
+
+    Ok(())
 }
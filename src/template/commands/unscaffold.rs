@@ -0,0 +1,191 @@
+use std::{fs, io};
+
+use crate::template::answers::Answers;
+use crate::template::output::{self, Event, Marker};
+use crate::template::timings::Timings;
+use crate::template::{Day, Error, readme_benchmarks, workspace};
+
+const ARCHIVE_DIR: &str = "data/archive";
+
+/// Directories that contain the files of a day, named either `{day}.*` or `{day}-*`.
+const DAY_DIRS: [&str; 4] = ["src/bin", "data/inputs", "data/examples", "data/puzzles"];
+
+/// Collects the paths of all files that belong to a day, including extra examples, named
+/// examples and backups.
+fn find_day_files(day: Day) -> Result<Vec<String>, Error> {
+    let prefixes = [format!("{day}."), format!("{day}-")];
+    let mut paths = vec![];
+
+    for dir in DAY_DIRS {
+        let entries = match fs::read_dir(workspace::path(dir)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(Error::Io(format!("Failed to read \"{dir}\""), e)),
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let file_type = entry.file_type().ok()?;
+                let is_day_file =
+                    file_type.is_file() && prefixes.iter().any(|prefix| name.starts_with(prefix));
+                // named examples live in a directory named after the day.
+                let is_day_dir = file_type.is_dir() && name == day.to_string();
+                (is_day_file || is_day_dir).then_some(name)
+            })
+            .collect();
+
+        names.sort_unstable();
+        paths.extend(names.into_iter().map(|name| format!("{dir}/{name}")));
+    }
+
+    Ok(paths)
+}
+
+/// Returns an archive directory for a day that does not exist yet, so that archiving a day
+/// twice keeps both copies.
+fn get_archive_dir(day: Day) -> String {
+    let mut dir = format!("{ARCHIVE_DIR}/{day}");
+    let mut i = 1;
+    while workspace::path(&dir).exists() {
+        dir = format!("{ARCHIVE_DIR}/{day}.{i}");
+        i += 1;
+    }
+    dir
+}
+
+fn archive_file(path: &str, archive_dir: &str) -> Result<String, Error> {
+    let destination = format!("{archive_dir}/{path}");
+
+    if let Some(parent) = workspace::path(&destination).parent() {
+        fs::create_dir_all(parent).map_err(Error::io("Failed to create archive directory"))?;
+    }
+
+    fs::rename(workspace::path(path), workspace::path(&destination))
+        .map_err(Error::io("Failed to archive file"))?;
+    Ok(destination)
+}
+
+fn print_planned(action: &str, path: &str, message: &str) {
+    if output::is_json() {
+        Event::new("planned")
+            .with("path", path.to_string())
+            .with("action", action.to_string())
+            .emit();
+    } else {
+        println!("{message}");
+    }
+}
+
+/// Removes all files of a day, or moves them to `data/archive/{day}` if `archive` is set.
+/// The stored timings and answers of the day are dropped and the benchmark table in the readme
+/// is updated. With `dry_run`, only the planned actions are printed.
+pub fn handle(day: Day, archive: bool, dry_run: bool) -> Result<(), Error> {
+    let paths = find_day_files(day)?;
+
+    let mut timings = Timings::read_from_file();
+    let has_timings = timings.remove(day);
+
+    let mut answers = Answers::read_from_file();
+    let has_answers = answers.remove(day);
+
+    if paths.is_empty() && !has_timings && !has_answers {
+        if output::is_json() {
+            Event::new("unscaffolded")
+                .with("day", day.to_string())
+                .with("timings", false)
+                .with("answers", false)
+                .emit();
+        } else {
+            println!("Nothing to remove for day {day}.");
+        }
+        return Ok(());
+    }
+
+    let archive_dir = get_archive_dir(day);
+
+    if dry_run {
+        for path in &paths {
+            if archive {
+                let message = format!("Would archive \"{path}\" to \"{archive_dir}/{path}\"");
+                print_planned("archive", path, &message);
+            } else {
+                print_planned("remove", path, &format!("Would remove \"{path}\""));
+            }
+        }
+        if has_timings {
+            let message = format!("Would remove the stored timings of day {day}");
+            print_planned("remove", "data/timings.json", &message);
+        }
+        if has_answers {
+            let message = format!("Would remove the accepted answers of day {day}");
+            print_planned("remove", "data/answers.json", &message);
+        }
+        return Ok(());
+    }
+
+    for path in &paths {
+        if archive {
+            let destination = archive_file(path, &archive_dir)?;
+            if output::is_json() {
+                Event::new("archived")
+                    .with("path", path.clone())
+                    .with("archive", destination)
+                    .emit();
+            } else {
+                println!("Archived \"{path}\" to \"{destination}\"");
+            }
+        } else {
+            if workspace::path(path).is_dir() {
+                fs::remove_dir_all(workspace::path(path))
+                    .map_err(Error::io("Failed to remove directory"))?;
+            } else {
+                fs::remove_file(workspace::path(path))
+                    .map_err(Error::io("Failed to remove file"))?;
+            }
+            if output::is_json() {
+                Event::new("removed").with("path", path.clone()).emit();
+            } else {
+                println!("Removed \"{path}\"");
+            }
+        }
+    }
+
+    if has_timings {
+        timings
+            .store_file()
+            .map_err(Error::io("failed to store timings"))?;
+        readme_benchmarks::update(timings)?;
+
+        if !output::is_json() {
+            println!("Removed the stored timings of day {day} and updated the benchmarks.");
+        }
+    }
+
+    if has_answers {
+        answers
+            .store_file()
+            .map_err(Error::io("failed to store answers"))?;
+
+        if !output::is_json() {
+            println!("Removed the accepted answers of day {day}.");
+        }
+    }
+
+    if output::is_json() {
+        Event::new("unscaffolded")
+            .with("day", day.to_string())
+            .with("timings", has_timings)
+            .with("answers", has_answers)
+            .emit();
+    } else {
+        println!("---");
+        println!(
+            "{} Type `cargo scaffold {day}` to start over.",
+            Marker::Tree
+        );
+    }
+
+    Ok(())
+}
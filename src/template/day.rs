@@ -141,7 +141,7 @@ macro_rules! day {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(test)]
 mod tests {
     use super::{Day, all_days};
 
@@ -0,0 +1,165 @@
+use std::{fmt::Display, io, process::ExitStatus};
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::{readme_benchmarks, run_multi};
+
+/// Exit code for invalid command-line arguments.
+pub const EXIT_USAGE: i32 = 2;
+
+/// Exit code of a solution if one of its parts returned `None`.
+pub const EXIT_UNSOLVED: i32 = 8;
+
+/// Exit code of a solution if one of its parts returned a result that differs from the accepted answer
+/// or that was rejected when submitting it.
+pub const EXIT_WRONG_ANSWER: i32 = 9;
+
+/// Exit code of `solve` if the solution failed otherwise, e.g. did not compile or panicked.
+pub const EXIT_SOLUTION_FAILED: i32 = 10;
+
+/// An error that can occur while running one of the template commands.
+///
+/// Every variant maps to a distinct process exit code, see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// The command-line arguments are invalid.
+    Usage(String),
+    /// Reading or writing a file failed.
+    Io(String, io::Error),
+    /// `aoc-cli` is not installed or a call to it failed.
+    AocCli(AocCommandError),
+    /// A child `cargo` command could not be spawned or its output could not be read.
+    Command(io::Error),
+    /// The benchmark table in the readme could not be updated.
+    Readme(String),
+    /// The `today` command was invoked outside of advent.
+    NotAdvent,
+    /// A solution did not compile, panicked or did not solve all parts.
+    ///
+    /// [`EXIT_UNSOLVED`] and [`EXIT_WRONG_ANSWER`] are passed on, every other failure maps to
+    /// [`EXIT_SOLUTION_FAILED`], so that it can't be mistaken for an error of this process.
+    Solution(ExitStatus),
+}
+
+impl Error {
+    /// Convenience constructor for [`Error::Io`], e.g. `.map_err(Error::io("failed to read file"))`.
+    pub fn io(context: &str) -> impl FnOnce(io::Error) -> Self + '_ {
+        move |e| Error::Io(context.into(), e)
+    }
+
+    /// The exit code the process should terminate with when encountering this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => EXIT_USAGE,
+            Error::Io(..) => 3,
+            Error::AocCli(_) => 4,
+            Error::Command(_) => 5,
+            Error::Readme(_) => 6,
+            Error::NotAdvent => 7,
+            Error::Solution(status) => match status.code() {
+                Some(code @ (EXIT_UNSOLVED | EXIT_WRONG_ANSWER)) => code,
+                _ => EXIT_SOLUTION_FAILED,
+            },
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(e) => write!(f, "{e}"),
+            Error::Io(context, e) => write!(f, "{context}: {e}"),
+            Error::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Command(e) => write!(f, "failed to run cargo: {e}"),
+            Error::Readme(e) => write!(f, "failed to store updated benchmarks: {e}"),
+            Error::NotAdvent => write!(
+                f,
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+            ),
+            Error::Solution(status) => match status.code() {
+                Some(EXIT_UNSOLVED) => write!(f, "not all parts returned a result."),
+                Some(EXIT_WRONG_ANSWER) => {
+                    write!(
+                        f,
+                        "a part returned a result that differs from the accepted answer."
+                    )
+                }
+                _ => write!(f, "solution failed with {status}."),
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+impl From<run_multi::Error> for Error {
+    fn from(e: run_multi::Error) -> Self {
+        match e {
+            run_multi::Error::BrokenPipe => Error::Command(io::ErrorKind::BrokenPipe.into()),
+            run_multi::Error::IO(e) => Error::Command(e),
+        }
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        match e {
+            readme_benchmarks::Error::Parser(e) => Error::Readme(e),
+            readme_benchmarks::Error::IO(e) => Error::Io("failed to update README.md".into(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EXIT_SOLUTION_FAILED, EXIT_UNSOLVED, EXIT_USAGE, EXIT_WRONG_ANSWER, Error};
+    use crate::template::aoc_cli::AocCommandError;
+    use std::{collections::HashSet, io};
+
+    #[test]
+    fn uses_distinct_exit_codes() {
+        let errors = [
+            Error::Io("foo".into(), io::ErrorKind::NotFound.into()),
+            Error::AocCli(AocCommandError::CommandNotFound),
+            Error::Command(io::ErrorKind::BrokenPipe.into()),
+            Error::Readme("bar".into()),
+            Error::NotAdvent,
+        ];
+
+        let codes: HashSet<i32> = errors.iter().map(Error::exit_code).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&EXIT_USAGE));
+        assert!(!codes.contains(&EXIT_UNSOLVED));
+        assert!(!codes.contains(&EXIT_WRONG_ANSWER));
+        assert!(!codes.contains(&EXIT_SOLUTION_FAILED));
+        assert!(!codes.contains(&0));
+
+        assert_eq!(Error::Usage("baz".into()).exit_code(), EXIT_USAGE);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn maps_solution_failures_to_a_dedicated_exit_code() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        // a raw wait status holds the exit code in its second byte, or the signal in its first.
+        let exit_code = |status| Error::Solution(ExitStatus::from_raw(status)).exit_code();
+
+        assert_eq!(exit_code(EXIT_UNSOLVED << 8), EXIT_UNSOLVED);
+        assert_eq!(exit_code(EXIT_WRONG_ANSWER << 8), EXIT_WRONG_ANSWER);
+        assert_eq!(exit_code(3 << 8), EXIT_SOLUTION_FAILED);
+        assert_eq!(exit_code(101 << 8), EXIT_SOLUTION_FAILED);
+        assert_eq!(exit_code(9), EXIT_SOLUTION_FAILED);
+    }
+}
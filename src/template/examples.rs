@@ -0,0 +1,277 @@
+/// Module for named examples that are stored as `data/examples/{day}/{name}.txt`.
+///
+/// The expected answers live next to them in `data/examples/{day}/expected.json`, keyed by the
+/// name of the example:
+///
+/// ```json
+/// {
+///   "example": { "part_1": 13, "part_2": "43" },
+///   "single_row": { "part_1": 1, "part_2": null, "params": { "steps": 6 } }
+/// }
+/// ```
+///
+/// Answers are compared as strings. A missing or `null` answer is not checked.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, workspace};
+
+const EXPECTATIONS_FILE_NAME: &str = "expected.json";
+
+/// Expected answers and parameters of a single example.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expectation {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub params: HashMap<String, String>,
+}
+
+/// A named example input along with its expectations.
+///
+/// Dereferences to the input, so it can be passed to solution parts as is.
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expectation: Expectation,
+}
+
+impl Example {
+    /// Reads the example `name` of a day.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the example or its expected answers can not be read, this is meant to be used
+    /// in tests.
+    #[must_use]
+    pub fn read(day: Day, name: &str) -> Self {
+        let dir = get_examples_dir(day);
+        let path = dir.join(format!("{name}.txt"));
+        let input = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not open example {}: {e}", path.display()));
+
+        let path = dir.join(EXPECTATIONS_FILE_NAME);
+        let mut expectations = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| parse_expectations(&s))
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+
+        let expectation = expectations
+            .remove(name)
+            .unwrap_or_else(|| panic!("no expectations for \"{name}\" in {}", path.display()));
+
+        Self {
+            name: name.into(),
+            input,
+            expectation,
+        }
+    }
+
+    /// The expected answer for a part, if it should be checked.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.expectation.part_1.as_deref(),
+            2 => self.expectation.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Parses a parameter of the example, e.g. a number of steps that differs from the one of
+    /// the real input.
+    pub fn param<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.expectation.params.get(key)?.parse().ok()
+    }
+}
+
+impl Deref for Example {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+/// Returns the directory of the named examples of a day.
+#[must_use]
+pub fn get_examples_dir(day: Day) -> PathBuf {
+    workspace::path(Path::new("data").join("examples").join(day.to_string()))
+}
+
+/// Asserts that the result of a part matches the expected answer of an example, if there is one.
+///
+/// # Panics
+///
+/// Panics if the result differs from the expected answer.
+pub fn check<T: Display>(example: &Example, part: u8, result: Option<T>) {
+    if let Some(expected) = example.expected(part) {
+        assert_eq!(
+            result.map(|result| result.to_string()).as_deref(),
+            Some(expected),
+            "example \"{}\", part {part}",
+            example.name
+        );
+    }
+}
+
+/// Parses the contents of an expectations file.
+pub fn parse_expectations(s: &str) -> Result<HashMap<String, Expectation>, String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+    let entries = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?;
+
+    entries
+        .iter()
+        .map(|(name, value)| {
+            let fields = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or_else(|| format!("expected `{name}` to be an object."))?;
+
+            let part = |key: &str| match fields.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(value) => to_plain_string(value)
+                    .map(Some)
+                    .ok_or_else(|| format!("expected `{name}.{key}` to be a string or number.")),
+            };
+
+            let params = match fields.get("params") {
+                None | Some(JsonValue::Null) => HashMap::new(),
+                Some(JsonValue::Object(params)) => params
+                    .iter()
+                    .map(|(key, value)| {
+                        to_plain_string(value)
+                            .map(|value| (key.clone(), value))
+                            .ok_or_else(|| {
+                                format!("expected `{name}.params.{key}` to be a string or number.")
+                            })
+                    })
+                    .collect::<Result<_, _>>()?,
+                Some(_) => return Err(format!("expected `{name}.params` to be an object.")),
+            };
+
+            Ok((
+                name.clone(),
+                Expectation {
+                    part_1: part("part_1")?,
+                    part_2: part("part_2")?,
+                    params,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Formats strings, numbers and booleans the way a solution would print them.
+fn to_plain_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Number(n) if n.fract() == 0.0 => Some(format!("{n:.0}")),
+        JsonValue::Number(n) => Some(n.to_string()),
+        JsonValue::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Generates one test per named example, checking the results of `part_one` and `part_two`
+/// against the expected answers in `data/examples/{day}/expected.json`.
+///
+/// Examples are named after the test, e.g. `example_tests!(example, single_row);` reads
+/// `example.txt` and `single_row.txt`. Prefix the names with `1;` or `2;` to only check a
+/// single part, as with [`solution!`](crate::solution).
+#[macro_export]
+macro_rules! example_tests {
+    (@impl $parts:tt; $($name:ident),+) => {
+        $( $crate::example_tests!(@test $name $parts); )+
+    };
+
+    (@test $name:ident { $( [$func:expr, $part:expr] )* }) => {
+        #[test]
+        fn $name() {
+            let example = $crate::template::examples::Example::read(DAY, stringify!($name));
+            $( $crate::template::examples::check(&example, $part, $func(&example)); )*
+        }
+    };
+
+    (1; $($name:ident),+ $(,)?) => {
+        $crate::example_tests!(@impl { [part_one, 1] }; $($name),+);
+    };
+    (2; $($name:ident),+ $(,)?) => {
+        $crate::example_tests!(@impl { [part_two, 2] }; $($name),+);
+    };
+    ($($name:ident),+ $(,)?) => {
+        $crate::example_tests!(@impl { [part_one, 1] [part_two, 2] }; $($name),+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Example, Expectation, check, parse_expectations};
+
+    #[test]
+    fn parses_expectations() {
+        let json = r#"{
+            "example": { "part_1": 13, "part_2": "43" },
+            "single_row": { "part_1": 1, "part_2": null, "params": { "steps": 6, "mode": "fast" } },
+            "empty": {}
+        }"#;
+
+        let expectations = parse_expectations(json).unwrap();
+        assert_eq!(expectations.len(), 3);
+        assert_eq!(expectations["example"].part_1.as_deref(), Some("13"));
+        assert_eq!(expectations["example"].part_2.as_deref(), Some("43"));
+        assert_eq!(expectations["single_row"].part_2, None);
+        assert_eq!(expectations["single_row"].params["steps"], "6");
+        assert_eq!(expectations["single_row"].params["mode"], "fast");
+        assert_eq!(expectations["empty"], Expectation::default());
+    }
+
+    #[test]
+    fn rejects_malformed_expectations() {
+        assert!(parse_expectations("[]").is_err());
+        assert!(parse_expectations(r#"{ "example": 1 }"#).is_err());
+        assert!(parse_expectations(r#"{ "example": { "part_1": [] } }"#).is_err());
+        assert!(parse_expectations(r#"{ "example": { "params": 1 } }"#).is_err());
+    }
+
+    fn get_example() -> Example {
+        let expectations =
+            parse_expectations(r#"{ "example": { "part_1": 2, "params": { "steps": 6 } } }"#)
+                .unwrap();
+
+        Example {
+            name: "example".into(),
+            input: "1\n1".into(),
+            expectation: expectations["example"].clone(),
+        }
+    }
+
+    #[test]
+    fn reads_params() {
+        let example = get_example();
+        assert_eq!(example.param::<u32>("steps"), Some(6));
+        assert_eq!(example.param::<u32>("missing"), None);
+    }
+
+    #[test]
+    fn checks_expected_answers() {
+        let example = get_example();
+        let part_one = |input: &str| Some(input.lines().count());
+        check(&example, 1, part_one(&example));
+        check(&example, 2, None::<u64>);
+    }
+
+    #[test]
+    #[should_panic(expected = "example \"example\", part 1")]
+    fn panics_on_wrong_answers() {
+        check(&get_example(), 1, Some(3));
+    }
+}
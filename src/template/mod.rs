@@ -1,12 +1,19 @@
-use std::{env, fs};
+use std::{fs, path::Path};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod examples;
+pub mod output;
 pub mod runner;
+pub mod workspace;
 
 pub use day::*;
+pub use error::{EXIT_SOLUTION_FAILED, EXIT_UNSOLVED, EXIT_USAGE, EXIT_WRONG_ANSWER, Error};
 
+mod answers;
 mod day;
+mod error;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
@@ -18,8 +25,7 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = workspace::path(Path::new("data").join(folder).join(format!("{day}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -27,15 +33,34 @@ pub fn read_file(folder: &str, day: Day) -> String {
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = workspace::path(
+        Path::new("data")
+            .join(folder)
+            .join(format!("{day}-{part}.txt")),
+    );
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Converts CRLF line endings to LF, optionally removing line breaks at the end of the input.
+///
+/// Other whitespace is kept, as some puzzles align columns with trailing spaces.
+#[must_use]
+pub fn normalize_input(input: &str, trim_end: bool) -> String {
+    let input = input.replace("\r\n", "\n");
+    if trim_end {
+        input.trim_end_matches('\n').to_string()
+    } else {
+        input
+    }
+}
+
+/// Like [`read_file`], but with LF line endings only and optionally without trailing line breaks.
+#[must_use]
+pub fn read_file_normalized(folder: &str, day: Day, trim_end: bool) -> String {
+    normalize_input(&read_file(folder, day), trim_end)
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
@@ -62,7 +87,21 @@ macro_rules! solution {
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            warn_on_crlf(&input, DAY);
+            let outcomes = [$( run_part($func, &input, DAY, $part), )*];
+            exit_with(&outcomes);
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_input;
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_input("a\r\nb \r\n\r\n", false), "a\nb \n\n");
+        assert_eq!(normalize_input("a\r\nb \r\n\r\n", true), "a\nb ");
+        assert_eq!(normalize_input("a\n", true), "a");
+    }
+}
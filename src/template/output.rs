@@ -0,0 +1,252 @@
+/// Module that controls how command-line output is presented.
+///
+/// In JSON mode, every command prints one JSON object per line to stdout, each with an `event` key
+/// describing what happened. Errors are printed as `error` events to stderr.
+///
+/// Text output is styled with ANSI escape sequences, emoji and in-place updates only if stdout is
+/// a terminal, unless overridden with `--color=always|never|auto` or the `NO_COLOR` variable.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    io::{self, IsTerminal},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static JSON: OnceLock<bool> = OnceLock::new();
+static STYLED: OnceLock<bool> = OnceLock::new();
+
+/// Enables or disables JSON output for this process. Has no effect after the first call to
+/// [`is_json`], which settles the mode.
+pub fn set_json(enabled: bool) {
+    let _ = JSON.set(enabled);
+}
+
+/// Returns true if output should be printed as JSON events.
+///
+/// Unless set explicitly, this mirrors the `--json` flag so child invocations pick it up as well.
+pub fn is_json() -> bool {
+    *JSON.get_or_init(|| env::args().any(|x| x == "--json"))
+}
+
+/// Whether text output should be styled, as passed via `--color`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn resolve(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|x| x.is_empty()) && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "invalid color choice \"{s}\", expecting one of: auto, always, never"
+            )),
+        }
+    }
+}
+
+/// Sets the color choice for this process. Has no effect after the first call to [`is_styled`],
+/// which settles the choice.
+pub fn set_color(choice: ColorChoice) {
+    let _ = STYLED.set(choice.resolve());
+}
+
+/// Returns true if text output should use ANSI styles, emoji and in-place updates.
+///
+/// Unless set explicitly, this mirrors the `--color` flag so child invocations pick it up as well.
+pub fn is_styled() -> bool {
+    *STYLED.get_or_init(|| color_from_args().unwrap_or(ColorChoice::Auto).resolve())
+}
+
+/// The `--color` argument that makes child invocations behave like this process.
+pub fn color_arg() -> String {
+    let choice = if is_styled() { "always" } else { "never" };
+    format!("--color={choice}")
+}
+
+fn color_from_args() -> Option<ColorChoice> {
+    let args: Vec<String> = env::args().collect();
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if let Some(value) = arg.strip_prefix("--color=") {
+            value.parse().ok()
+        } else if arg == "--color" {
+            args.get(i + 1)?.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+fn style(code: &str, value: impl Display) -> String {
+    if is_styled() {
+        format!("{code}{value}{ANSI_RESET}")
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats a value as bold text if output is styled.
+pub fn bold(value: impl Display) -> String {
+    style(ANSI_BOLD, value)
+}
+
+/// Formats a value as italic text if output is styled.
+pub fn italic(value: impl Display) -> String {
+    style(ANSI_ITALIC, value)
+}
+
+/// Status markers that fall back to plain ASCII if output is not styled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    /// Prefixes success and hint messages.
+    Tree,
+    /// A part that did not return a result.
+    Cross,
+    /// A part with a result spanning multiple lines.
+    MultiLine,
+    /// Something that is present or done.
+    Check,
+}
+
+impl Display for Marker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (styled, plain) = match self {
+            Marker::Tree => ("🎄", "*"),
+            Marker::Cross => ("✖", "x"),
+            Marker::MultiLine => ("▼", "v"),
+            Marker::Check => ("✓", "+"),
+        };
+        f.write_str(if is_styled() { styled } else { plain })
+    }
+}
+
+/// A single JSON output event.
+pub struct Event {
+    fields: HashMap<String, JsonValue>,
+}
+
+impl Event {
+    pub fn new(name: &str) -> Self {
+        let mut fields = HashMap::new();
+        fields.insert("event".into(), JsonValue::String(name.into()));
+        Self { fields }
+    }
+
+    #[must_use]
+    pub fn with(mut self, key: &str, value: impl Into<JsonValue>) -> Self {
+        self.fields.insert(key.into(), value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_opt(self, key: &str, value: Option<impl Into<JsonValue>>) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self.with(key, ()),
+        }
+    }
+
+    /// Serializes the event to a single line of JSON.
+    pub fn to_json(&self) -> String {
+        // NOTE: stringifying only fails for non-finite numbers, which events do not contain.
+        JsonValue::Object(self.fields.clone())
+            .stringify()
+            .unwrap_or_default()
+    }
+
+    /// Prints the event to stdout.
+    pub fn emit(&self) {
+        println!("{}", self.to_json());
+    }
+}
+
+/// Prints an error message to stderr, formatted as an `error` event in JSON mode.
+pub fn error(message: &str) {
+    if is_json() {
+        eprintln!(
+            "{}",
+            Event::new("error")
+                .with("message", message.to_string())
+                .to_json()
+        );
+    } else {
+        eprintln!("Error: {message}");
+    }
+}
+
+/// Prints a warning to stderr, formatted as a `warning` event in JSON mode.
+pub fn warning(message: &str) {
+    if is_json() {
+        eprintln!(
+            "{}",
+            Event::new("warning")
+                .with("message", message.to_string())
+                .to_json()
+        );
+    } else {
+        eprintln!("Warning: {message}");
+    }
+}
+
+/// Parses a line of output as an event, returning its fields if it is one.
+pub fn parse_event(line: &str) -> Option<HashMap<String, JsonValue>> {
+    let json: JsonValue = line.parse().ok()?;
+    let fields = json.get::<HashMap<String, JsonValue>>()?;
+    fields.get("event")?.get::<String>()?;
+    Some(fields.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorChoice, Event, parse_event};
+
+    #[test]
+    fn parses_color_choices() {
+        assert_eq!("auto".parse::<ColorChoice>(), Ok(ColorChoice::Auto));
+        assert_eq!("always".parse::<ColorChoice>(), Ok(ColorChoice::Always));
+        assert_eq!("never".parse::<ColorChoice>(), Ok(ColorChoice::Never));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn round_trips_events() {
+        let line = Event::new("part")
+            .with("part", 1.0)
+            .with_opt("result", None::<String>)
+            .to_json();
+        let fields = parse_event(&line).unwrap();
+        assert_eq!(fields["event"].get::<String>().unwrap(), "part");
+        assert_eq!(*fields["part"].get::<f64>().unwrap(), 1.0);
+        assert!(fields["result"].is_null());
+    }
+
+    #[test]
+    fn ignores_plain_lines() {
+        assert!(parse_event("Part 1: 42 (1.0ms)").is_none());
+        assert!(parse_event(r#"{ "foo": 1 }"#).is_none());
+    }
+}
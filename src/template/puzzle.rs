@@ -0,0 +1,183 @@
+/// Module that extracts information from puzzle descriptions downloaded by aoc-cli.
+use std::fs;
+
+use crate::template::{Day, workspace};
+
+#[must_use]
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+/// Reads the puzzle description for a day, if it has been downloaded.
+pub fn read_description(day: Day) -> Option<String> {
+    fs::read_to_string(workspace::path(get_puzzle_path(day))).ok()
+}
+
+/// Extracts the puzzle title, e.g. `Day 1: Trebuchet?!`, from a puzzle description.
+pub fn parse_title(description: &str) -> Option<&str> {
+    let line = description.lines().find(|l| l.contains("--- Day "))?;
+    let start = line.find("Day ")?;
+    let title = line[start..].trim_end().strip_suffix("---")?.trim_end();
+    Some(title)
+}
+
+/// Splits a puzzle description into the sections of part one and (if unlocked) part two.
+pub fn split_parts(description: &str) -> (&str, Option<&str>) {
+    match description.find("--- Part Two ---") {
+        Some(index) => (&description[..index], Some(&description[index..])),
+        None => (description, None),
+    }
+}
+
+/// Extracts candidate example inputs from a section of a puzzle description.
+///
+/// aoc-cli converts `<pre><code>` blocks to fenced markdown code blocks, raw HTML blocks are
+/// supported as well. A trailing newline is removed from each block.
+pub fn parse_examples(section: &str) -> Vec<String> {
+    let mut blocks: Vec<(usize, String)> = vec![];
+
+    let mut offset = 0;
+    let mut block: Option<(usize, Vec<&str>)> = None;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some((start, content)) => blocks.push((start, content.join("\n"))),
+                None => block = Some((offset, vec![])),
+            }
+        } else if let Some((_, content)) = block.as_mut() {
+            content.push(line);
+        }
+        offset += line.len() + 1;
+    }
+
+    let mut rest = section;
+    let mut consumed = 0;
+    while let Some(start) = rest.find("<pre><code>") {
+        let content_start = start + "<pre><code>".len();
+        let Some(end) = rest[content_start..].find("</code></pre>") else {
+            break;
+        };
+        let content = &rest[content_start..content_start + end];
+        blocks.push((
+            consumed + start,
+            decode_entities(content.strip_suffix('\n').unwrap_or(content)),
+        ));
+        consumed += content_start + end;
+        rest = &rest[content_start + end..];
+    }
+
+    blocks.sort_by_key(|(start, _)| *start);
+    blocks
+        .into_iter()
+        .map(|(_, block)| block)
+        .filter(|block| !block.trim().is_empty())
+        .collect()
+}
+
+/// Extracts the example answer from a section of a puzzle description.
+///
+/// This is the last emphasised code value, e.g. `` `*142*` `` or `<code><em>142</em></code>`.
+pub fn parse_example_answer(section: &str) -> Option<String> {
+    const PATTERNS: [(&str, &str); 4] = [
+        ("`*", "*`"),
+        ("*`", "`*"),
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ];
+
+    PATTERNS
+        .iter()
+        .filter_map(|(open, close)| {
+            let start = section.rfind(open)?;
+            let value_start = start + open.len();
+            let end = section[value_start..].find(close)?;
+            let value = &section[value_start..value_start + end];
+            (!value.is_empty() && !value.contains('\n')).then_some((start, value))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, value)| decode_entities(value))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_example_answer, parse_examples, parse_title, split_parts};
+
+    const DESCRIPTION: &str = "\\--- Day 1: Trebuchet?! ---
+----------
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, adding these together produces `*142*`.
+
+\\--- Part Two ---
+----------
+
+For example:
+
+```
+two1nine
+```
+
+Adding these together produces *`281`*.
+";
+
+    #[test]
+    fn splits_parts() {
+        let (part_one, part_two) = split_parts(DESCRIPTION);
+        assert!(part_one.contains("142"));
+        assert!(!part_one.contains("281"));
+        assert!(part_two.unwrap().contains("281"));
+        assert_eq!(split_parts("foo").1, None);
+    }
+
+    #[test]
+    fn parses_fenced_examples() {
+        let (part_one, part_two) = split_parts(DESCRIPTION);
+        assert_eq!(parse_examples(part_one), vec!["1abc2\npqr3stu8vwx"]);
+        assert_eq!(parse_examples(part_two.unwrap()), vec!["two1nine"]);
+    }
+
+    #[test]
+    fn parses_html_examples() {
+        let section =
+            "<p>Example:</p>\n<pre><code>a &lt; b\n1\n</code></pre>\n<pre><code>2</code></pre>";
+        assert_eq!(parse_examples(section), vec!["a < b\n1", "2"]);
+    }
+
+    #[test]
+    fn parses_example_answers() {
+        let (part_one, part_two) = split_parts(DESCRIPTION);
+        assert_eq!(parse_example_answer(part_one), Some("142".into()));
+        assert_eq!(parse_example_answer(part_two.unwrap()), Some("281".into()));
+        assert_eq!(
+            parse_example_answer("is <code><em>-3</em></code> and <code><em>7</em></code>"),
+            Some("7".into())
+        );
+        assert_eq!(parse_example_answer("no answer here"), None);
+    }
+
+    #[test]
+    fn parses_titles() {
+        let description = "\\--- Day 1: Trebuchet?! ---\n----------\n\nSomething is wrong.";
+        assert_eq!(parse_title(description), Some("Day 1: Trebuchet?!"));
+    }
+
+    #[test]
+    fn handles_missing_titles() {
+        assert_eq!(parse_title("Something is wrong."), None);
+    }
+}
@@ -1,48 +1,70 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::Day;
+use crate::template::output::{self, Event};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_record: bool,
+) -> Result<Option<Timings>, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
+    let is_json = output::is_json();
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if !is_json {
             if need_space {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("{}", output::bold(format!("Day {day}")));
             println!("------");
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, is_record)?;
 
-            if output.is_empty() {
-                println!("Not solved.");
+        if output.is_empty() {
+            if is_json {
+                Event::new("unsolved").with("day", day.to_string()).emit();
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                println!("Not solved.");
             }
-        });
+        } else if is_json {
+            let val = child_commands::parse_exec_time_events(&output, day);
+            timings.push(val);
+        } else {
+            let val = child_commands::parse_exec_time(&output, day);
+            timings.push(val);
+        }
+    }
 
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
+        if is_json {
+            Event::new("total")
+                .with("total_millis", total_millis)
+                .emit();
+        } else {
+            println!(
+                "\n{} {}",
+                output::bold("Total (Run):"),
+                output::italic(format!("{total_millis:.2}ms"))
+            );
+        }
+        Ok(Some(timings))
     } else {
-        None
+        Ok(None)
     }
 }
 
@@ -68,39 +90,60 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::workspace::{self, Workspace};
+    use crate::template::{Day, output};
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_record: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !workspace::path(get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
         let day_padded = day.to_string();
+        let color_arg = output::color_arg();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        args.push("--");
+
+        // child output is piped, so the color choice has to be passed on explicitly.
+        args.push(&color_arg);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if is_record {
+            // mirror `--record` flag to child invocations.
+            args.push("--record");
+        }
+
+        if output::is_json() {
+            // mirror `--json` flag to child invocations.
+            args.push("--json");
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .current_dir(Workspace::current().root())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -116,9 +159,16 @@ pub mod child_commands {
             });
         });
 
+        let is_json = output::is_json();
+
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            // in JSON mode, stdout is reserved for events.
+            if is_json && output::parse_event(&line).is_none() {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
             output.push(line);
         }
 
@@ -144,7 +194,7 @@ pub mod child_commands {
                 }
 
                 let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
+                    output::warning(&format!("could not parse timings from line: {l}"));
                     return None;
                 };
 
@@ -164,6 +214,47 @@ pub mod child_commands {
         timings
     }
 
+    /// Collects timings from `part` events printed by a child invocation in JSON mode.
+    pub fn parse_exec_time_events(output: &[String], day: Day) -> super::Timing {
+        let mut timings = super::Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        };
+
+        output
+            .iter()
+            .filter_map(|l| output::parse_event(l))
+            .filter(|event| event["event"].get::<String>().is_some_and(|e| e == "part"))
+            .for_each(|event| {
+                let samples = event.get("samples").and_then(|v| v.get::<f64>());
+                // only benched runs produce timings, mirroring the text output.
+                if samples.is_none_or(|&samples| samples <= 1.0) {
+                    return;
+                }
+
+                let part = event.get("part").and_then(|v| v.get::<f64>());
+                let duration = event.get("duration").and_then(|v| v.get::<String>());
+                let nanos = event.get("duration_nanos").and_then(|v| v.get::<f64>());
+
+                let (Some(part), Some(duration), Some(nanos)) = (part, duration, nanos) else {
+                    output::warning(&format!("could not parse timings from event: {event:?}"));
+                    return;
+                };
+
+                if (*part - 1.0).abs() < f64::EPSILON {
+                    timings.part_1 = Some(duration.clone());
+                } else {
+                    timings.part_2 = Some(duration.clone());
+                }
+
+                timings.total_nanos += nanos;
+            });
+
+        timings
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -190,7 +281,7 @@ pub mod child_commands {
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(test)]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -203,7 +294,7 @@ pub mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(test)]
     mod tests {
         use super::parse_exec_time;
 
@@ -239,6 +330,21 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_execution_time_events() {
+            let res = super::parse_exec_time_events(
+                &[
+                    r#"{"event":"part","part":1,"result":"0","duration":"74.1ns","duration_nanos":74.1,"samples":100000}"#.into(),
+                    r#"{"event":"part","part":2,"result":null,"duration":"1.0ms","duration_nanos":1000000,"samples":1}"#.into(),
+                    "not an event".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74.1_f64);
+            assert_eq!(res.part_1.unwrap(), "74.1ns");
+            assert!(res.part_2.is_none());
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -250,8 +356,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
         }
     }
 }
@@ -6,19 +6,184 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::Answers;
+use crate::template::commands::scaffold;
+use crate::template::output::{self, Event, Marker};
+use crate::template::{Day, EXIT_UNSOLVED, EXIT_WRONG_ANSWER, Error, aoc_cli};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The outcome of running a solution part, ordered by severity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PartOutcome {
+    /// The part returned a result that matches the accepted answer, or no answer is known yet.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned a result that differs from the accepted answer, or that was rejected
+    /// when submitting it.
+    WrongAnswer,
+    /// Submitting the result failed with an error, which is reported with the exit code of the
+    /// error.
+    Failed(i32),
+}
+
+impl PartOutcome {
+    /// The exit code the solution process should terminate with.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            PartOutcome::Solved => 0,
+            PartOutcome::Unsolved => EXIT_UNSOLVED,
+            PartOutcome::WrongAnswer => EXIT_WRONG_ANSWER,
+            PartOutcome::Failed(code) => code,
+        }
+    }
+}
+
+/// The exit code of the most severe outcome, or 0 if there are none.
+pub fn exit_code(outcomes: &[PartOutcome]) -> i32 {
+    outcomes
+        .iter()
+        .max()
+        .map_or(0, |outcome| outcome.exit_code())
+}
+
+/// Exits the solution process with the exit code of the most severe outcome.
+pub fn exit_with(outcomes: &[PartOutcome]) {
+    let code = exit_code(outcomes);
+    if code != 0 {
+        process::exit(code);
+    }
+}
+
+/// Warns if an input contains CRLF line endings, which solutions splitting on `"\n"` trip over.
+/// Enabled by setting the `AOC_WARN_CRLF` environment variable.
+pub fn warn_on_crlf(input: &str, day: Day) {
+    let is_enabled = env::var_os("AOC_WARN_CRLF").is_some_and(|x| !x.is_empty() && x != "0");
+    if is_enabled && input.contains("\r\n") {
+        output::warning(&format!(
+            "the input for day {day} has CRLF line endings. Use `read_file_normalized()` or `parse::sections()` to handle them."
+        ));
+    }
+}
+
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> PartOutcome {
     let part_str = format!("Part {part}");
+    let is_json = output::is_json();
+
+    let (result, duration, samples) = run_timed(func, input, |result| {
+        // intermediate results are overwritten in-place, which only works in a terminal.
+        if !is_json && output::is_styled() {
+            print_result(result, &part_str, "");
+        }
+    });
+
+    let result = result.map(|result| result.to_string());
+    let expected = Answers::read_from_file().get(day, part).map(String::from);
+    let is_correct = result
+        .as_ref()
+        .zip(expected.as_ref())
+        .map(|(result, expected)| result == expected);
+
+    if is_json {
+        print_event(
+            result.as_ref(),
+            expected.as_ref(),
+            is_correct,
+            day,
+            part,
+            &duration,
+            samples,
+        );
+    } else {
+        print_result(&result, &part_str, &format_duration(&duration, samples));
+    }
+
+    if let (Some(false), Some(expected)) = (is_correct, &expected) {
+        output::warning(&format!(
+            "{part_str} of day {day}: result differs from the accepted answer {expected}."
+        ));
+    }
+
+    let Some(result) = result else {
+        return PartOutcome::Unsolved;
+    };
+
+    let submitted = match submit_result(&result, day, part) {
+        Ok(submitted) => submitted,
+        Err(e) => {
+            output::error(&e.to_string());
+            return PartOutcome::Failed(e.exit_code());
+        }
+    };
+
+    // whether the website accepted the submitted result, if it decided either way.
+    let mut verdict = None;
+
+    if let Some(output) = submitted {
+        let accepted = aoc_cli::is_answer_accepted(&output);
+
+        if is_json {
+            Event::new("submit")
+                .with("day", day.to_string())
+                .with("part", f64::from(part))
+                .with("accepted", accepted)
+                .emit();
+        }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+        if accepted {
+            store_answer(day, part, &result);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+            if part == 1
+                && let Err(e) = scaffold::handle_part_two(day)
+            {
+                output::error(&format!("Failed to scaffold part two: {e}"));
+            }
+
+            verdict = Some(true);
+        } else if aoc_cli::is_answer_rejected(&output) {
+            verdict = Some(false);
+        }
+    }
+
+    // results of days that were solved before are recorded as their accepted answers, unless the
+    // website just said otherwise.
+    if expected.is_none() && verdict.is_none() && env::args().any(|x| x == "--record") {
+        store_answer(day, part, &result);
+
+        if is_json {
+            Event::new("recorded")
+                .with("day", day.to_string())
+                .with("part", f64::from(part))
+                .with("answer", result.clone())
+                .emit();
+        } else {
+            println!("{part_str}: recorded {result} as the accepted answer.");
+        }
+    }
+
+    solved_outcome(is_correct, verdict)
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// The outcome of a part that returned a result, given whether it matches the accepted answer
+/// and whether the website accepted it when it was submitted.
+fn solved_outcome(is_correct: Option<bool>, verdict: Option<bool>) -> PartOutcome {
+    match (verdict, is_correct) {
+        (Some(true), _) => PartOutcome::Solved,
+        (Some(false), _) | (None, Some(false)) => PartOutcome::WrongAnswer,
+        (None, _) => PartOutcome::Solved,
+    }
+}
+
+/// Remember an accepted answer so that `cargo status` and the golden tests can use it.
+fn store_answer(day: Day, part: u8, result: &str) {
+    let mut answers = Answers::read_from_file();
+    answers.set(day, part, result);
+    if let Err(e) = answers.store_file() {
+        output::error(&format!("Failed to store accepted answer: {e}"));
     }
 }
 
@@ -51,10 +216,11 @@ fn run_timed<I: Copy, T>(
 }
 
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if !output::is_json() && output::is_styled() {
+        let mut stdout = stdout();
+        print!(" > {}", output::italic("benching"));
+        let _ = stdout.flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
@@ -90,36 +256,63 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::too_many_arguments)]
+fn print_event<T: Display>(
+    result: Option<&T>,
+    expected: Option<&String>,
+    is_correct: Option<bool>,
+    day: Day,
+    part: u8,
+    duration: &Duration,
+    samples: u128,
+) {
+    Event::new("part")
+        .with("day", day.to_string())
+        .with("part", f64::from(part))
+        .with_opt("result", result.map(ToString::to_string))
+        .with_opt("expected", expected.cloned())
+        .with_opt("correct", is_correct)
+        .with("duration", format!("{duration:.1?}"))
+        .with("duration_nanos", duration.as_nanos() as f64)
+        .with("samples", samples as f64)
+        .emit();
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
+    // move back to the start of the line to replace the intermediate result.
+    let carriage_return = if output::is_styled() { "\r" } else { "" };
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: {} {duration_str}", Marker::MultiLine);
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    print!("{carriage_return}");
                     println!("{str}");
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {}{duration_str}", output::bold(result));
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    print!("{carriage_return}");
                     println!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                print!("{part}: {}", Marker::Cross);
+            } else if output::is_styled() {
+                print!("{carriage_return}");
+                println!("{part}: {}             ", Marker::Cross);
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: {}", Marker::Cross);
             }
         }
     }
@@ -128,40 +321,70 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Result<Option<Output>, Error> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+    let Some(part_index) = args.iter().position(|x| x == "--submit") else {
+        return Ok(None);
+    };
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let Some(part_submit) = args.get(part_index + 1).and_then(|x| x.parse::<u8>().ok()) else {
+        return Err(Error::Usage(
+            "Unexpected command-line input. Format: cargo solve 1 --submit 1".into(),
+        ));
+    };
+
+    if part_submit != part {
+        return Ok(None);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    aoc_cli::check()?;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    if !output::is_json() {
+        println!("Submitting result via aoc-cli...");
+    }
+    Ok(Some(aoc_cli::submit(day, part, &result.to_string())?))
+}
 
-    if part_submit != part {
-        return None;
+#[cfg(test)]
+mod tests {
+    use super::{PartOutcome, exit_code, solved_outcome};
+    use crate::template::{EXIT_UNSOLVED, EXIT_WRONG_ANSWER};
+
+    #[test]
+    fn maps_outcomes_to_exit_codes() {
+        assert_eq!(PartOutcome::Solved.exit_code(), 0);
+        assert_eq!(PartOutcome::Unsolved.exit_code(), EXIT_UNSOLVED);
+        assert_eq!(PartOutcome::WrongAnswer.exit_code(), EXIT_WRONG_ANSWER);
+        assert_eq!(PartOutcome::Failed(4).exit_code(), 4);
+        assert_eq!((EXIT_UNSOLVED, EXIT_WRONG_ANSWER), (8, 9));
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    #[test]
+    fn maps_results_to_outcomes() {
+        use PartOutcome::{Solved, WrongAnswer};
+
+        assert_eq!(solved_outcome(None, None), Solved);
+        assert_eq!(solved_outcome(Some(true), None), Solved);
+        assert_eq!(solved_outcome(Some(false), None), WrongAnswer);
+
+        // the website has the final say on submitted results.
+        assert_eq!(solved_outcome(None, Some(true)), Solved);
+        assert_eq!(solved_outcome(Some(false), Some(true)), Solved);
+        assert_eq!(solved_outcome(None, Some(false)), WrongAnswer);
+        assert_eq!(solved_outcome(Some(true), Some(false)), WrongAnswer);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn exits_with_the_most_severe_outcome() {
+        use PartOutcome::{Failed, Solved, Unsolved, WrongAnswer};
+
+        assert_eq!(exit_code(&[]), 0);
+        assert_eq!(exit_code(&[Solved, Solved]), 0);
+        assert_eq!(exit_code(&[Solved, Unsolved]), EXIT_UNSOLVED);
+        assert_eq!(exit_code(&[Unsolved, Solved]), EXIT_UNSOLVED);
+        assert_eq!(exit_code(&[WrongAnswer, Unsolved]), EXIT_WRONG_ANSWER);
+        assert_eq!(exit_code(&[Solved, WrongAnswer]), EXIT_WRONG_ANSWER);
+        assert_eq!(exit_code(&[Failed(4), WrongAnswer]), 4);
+    }
 }
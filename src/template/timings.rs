@@ -1,9 +1,9 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{Day, workspace};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_PATH: &str = "data/timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -25,13 +25,13 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(workspace::path(TIMINGS_FILE_PATH))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(workspace::path(TIMINGS_FILE_PATH))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,13 +51,21 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Remove the timing of a day, returning true if there was one.
+    pub fn remove(&mut self, day: Day) -> bool {
+        let len = self.data.len();
+        self.data.retain(|t| t.day != day);
+        self.data.len() != len
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        // NOTE: an empty float sum is `-0.0`, which would be printed as `-0.00ms`.
+        self.data.iter().fold(0.0, |acc, x| acc + x.total_nanos) / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -177,7 +185,7 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(test)]
 mod tests {
     use crate::day;
 
@@ -245,6 +253,28 @@ mod tests {
         }
     }
 
+    mod remove {
+        use crate::day;
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn removes_existing_days() {
+            let mut timings = get_mock_timings();
+            assert!(timings.remove(day!(2)));
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(timings.data[0].day, day!(1));
+            assert_eq!(timings.data[1].day, day!(4));
+        }
+
+        #[test]
+        fn ignores_missing_days() {
+            let mut timings = get_mock_timings();
+            assert!(!timings.remove(day!(3)));
+            assert_eq!(timings.data.len(), 3);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -253,7 +283,7 @@ mod tests {
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -285,7 +315,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -299,7 +329,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -313,7 +343,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
@@ -0,0 +1,96 @@
+/// Module that locates the project root, which all file operations of the template are relative to.
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+static CURRENT: OnceLock<Workspace> = OnceLock::new();
+
+/// The root directory of the project, i.e. the directory that contains `Cargo.toml`, `data` and
+/// `src/bin`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Locates the project root. In order of precedence, this is:
+    ///  1. the `AOC_ROOT` environment variable.
+    ///  2. the `CARGO_MANIFEST_DIR` environment variable, which cargo sets for `cargo run` and `cargo test`.
+    ///  3. the closest directory containing a `Cargo.toml`, starting at the current directory.
+    ///  4. the current directory.
+    #[must_use]
+    pub fn locate() -> Self {
+        let from_env = |key| {
+            env::var_os(key)
+                .filter(|x| !x.is_empty())
+                .map(PathBuf::from)
+        };
+
+        if let Some(root) = from_env("AOC_ROOT").or_else(|| from_env("CARGO_MANIFEST_DIR")) {
+            return Self::new(root);
+        }
+
+        let cwd = env::current_dir().unwrap_or_default();
+        let root = cwd
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").is_file())
+            .unwrap_or(&cwd);
+
+        Self::new(root)
+    }
+
+    /// The workspace of this process, located on first use unless set explicitly.
+    pub fn current() -> &'static Self {
+        CURRENT.get_or_init(Self::locate)
+    }
+
+    /// Sets the workspace for this process. Has no effect once a file has been accessed.
+    pub fn set_current(workspace: Self) {
+        let _ = CURRENT.set(workspace);
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolves a path relative to the project root, e.g. `data/inputs/01.txt`.
+    pub fn path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        self.root.join(relative)
+    }
+}
+
+/// Resolves a path relative to the root of the current [`Workspace`].
+pub fn path(relative: impl AsRef<Path>) -> PathBuf {
+    Workspace::current().path(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::Workspace;
+
+    #[test]
+    fn resolves_paths_relative_to_root() {
+        let workspace = Workspace::new("/tmp/aoc");
+        assert_eq!(
+            workspace.path("data/inputs/01.txt"),
+            Path::new("/tmp/aoc/data/inputs/01.txt")
+        );
+    }
+
+    #[test]
+    fn locates_the_manifest_dir() {
+        // cargo sets `CARGO_MANIFEST_DIR` when running tests.
+        let workspace = Workspace::locate();
+        assert!(workspace.path("Cargo.toml").is_file());
+        assert!(workspace.path("src/template").is_dir());
+    }
+}
@@ -0,0 +1,241 @@
+//! Helpers for property-based and differential tests of solutions, enabled with the `testing`
+//! feature.
+//!
+//! Every test case gets its own seeded random number generator, so failures can be reproduced
+//! by setting `AOC_SEED` to the seed printed in the failure message. The number of cases can be
+//! changed with `AOC_CASES`.
+use std::{env, fmt::Debug, ops::RangeInclusive};
+
+/// Number of cases a property is checked for, unless overridden with `AOC_CASES`.
+pub const DEFAULT_CASES: u64 = 256;
+
+/// A small, fast and deterministic random number generator (`SplitMix64`).
+///
+/// Not suitable for anything but generating test inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can not generate a number below 0");
+        #[allow(clippy::cast_possible_truncation)]
+        let value = ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64;
+        value
+    }
+
+    /// Returns a number within an inclusive range.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "can not generate a number in an empty range");
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.below(len),
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns an index in `0..len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.below(len as u64) as usize;
+        index
+    }
+
+    /// Returns true with a probability of `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// Picks a random element of a non-empty slice.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+/// Generates a grid of `width` by `height` cells, picked from `cells`, e.g. `b".#"`.
+pub fn grid(rng: &mut Rng, width: usize, height: usize, cells: &[u8]) -> String {
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| char::from(*rng.choose(cells)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generates `count` ranges like `3-7` within `bounds`, joined by `separator`.
+pub fn range_list(
+    rng: &mut Rng,
+    count: usize,
+    bounds: RangeInclusive<u64>,
+    separator: &str,
+) -> String {
+    (0..count)
+        .map(|_| {
+            let a = rng.range(bounds.clone());
+            let b = rng.range(bounds.clone());
+            format!("{}-{}", a.min(b), a.max(b))
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Generates `count` lines of comma-separated coordinates like `3,7,1`, each in `bounds`.
+pub fn coordinate_list(
+    rng: &mut Rng,
+    count: usize,
+    dimensions: usize,
+    bounds: RangeInclusive<u64>,
+) -> String {
+    (0..count)
+        .map(|_| {
+            (0..dimensions)
+                .map(|_| rng.range(bounds.clone()).to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generates `count` lines with one number each, within `bounds`.
+pub fn number_list(rng: &mut Rng, count: usize, bounds: RangeInclusive<u64>) -> String {
+    (0..count)
+        .map(|_| rng.range(bounds.clone()).to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn env_u64(key: &str) -> Option<u64> {
+    env::var(key).ok()?.parse().ok()
+}
+
+/// Runs `check` once per test case with a generator seeded by the case's seed.
+///
+/// Runs the seeds `0..AOC_CASES`, or only `AOC_SEED` if set.
+pub fn for_each_case(mut check: impl FnMut(&mut Rng, u64)) {
+    let seeds: Vec<u64> = match env_u64("AOC_SEED") {
+        Some(seed) => vec![seed],
+        None => (0..env_u64("AOC_CASES").unwrap_or(DEFAULT_CASES)).collect(),
+    };
+
+    for seed in seeds {
+        check(&mut Rng::new(seed), seed);
+    }
+}
+
+/// Asserts that two implementations return the same result for generated inputs.
+/// Prefer the [`differential!`](crate::differential) macro, which fills in the names.
+///
+/// # Panics
+///
+/// Panics with the seed and the input of the first case the implementations disagree on.
+pub fn assert_agree<I: Debug, T: PartialEq + Debug>(
+    names: [&str; 2],
+    generate: impl Fn(&mut Rng) -> I,
+    left: impl Fn(&I) -> T,
+    right: impl Fn(&I) -> T,
+) {
+    for_each_case(|rng, seed| {
+        let input = generate(rng);
+        let (left_result, right_result) = (left(&input), right(&input));
+        assert!(
+            left_result == right_result,
+            "`{}` and `{}` disagree for seed {seed}, rerun with AOC_SEED={seed}\n  input: {input:?}\n   left: {left_result:?}\n  right: {right_result:?}",
+            names[0],
+            names[1],
+        );
+    });
+}
+
+/// Asserts that two implementations agree on inputs produced by a generator, e.g.
+/// `differential!(part_two, part_two_naive, |rng| testing::range_list(rng, 3, 1..=1000, ","))`.
+///
+/// Both implementations are passed a reference to the generated input.
+#[macro_export]
+macro_rules! differential {
+    ($left:expr, $right:expr, $generate:expr $(,)?) => {
+        $crate::testing::assert_agree(
+            [stringify!($left), stringify!($right)],
+            $generate,
+            |input| $left(input),
+            |input| $right(input),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rng, assert_agree, coordinate_list, grid, range_list};
+
+    #[test]
+    fn is_deterministic() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn stays_within_ranges() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((5..=9).contains(&rng.range(5..=9)));
+            assert!(rng.below(3) < 3);
+        }
+        assert_eq!(rng.range(7..=7), 7);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn generates_puzzle_shaped_inputs() {
+        let mut rng = Rng::new(7);
+
+        let grid = grid(&mut rng, 4, 3, b".#");
+        assert_eq!(grid.lines().count(), 3);
+        assert!(grid.lines().all(|line| line.len() == 4));
+        assert!(grid.bytes().all(|b| b".#\n".contains(&b)));
+
+        let ranges = range_list(&mut rng, 5, 10..=20, ",");
+        for range in ranges.split(',') {
+            let (start, end) = range.split_once('-').unwrap();
+            let (start, end): (u64, u64) = (start.parse().unwrap(), end.parse().unwrap());
+            assert!(10 <= start && start <= end && end <= 20);
+        }
+
+        let coordinates = coordinate_list(&mut rng, 3, 3, 0..=9);
+        assert!(coordinates.lines().all(|line| line.split(',').count() == 3));
+    }
+
+    #[test]
+    fn passes_for_agreeing_implementations() {
+        crate::differential!(|n: &u64| n * 2, |n: &u64| n + n, |rng: &mut Rng| rng
+            .below(1000));
+    }
+
+    #[test]
+    #[should_panic(expected = "rerun with AOC_SEED=")]
+    fn fails_for_disagreeing_implementations() {
+        assert_agree(["a", "b"], |rng| rng.below(1000), |n| n / 2, |n| n >> 2);
+    }
+}
@@ -0,0 +1,257 @@
+//! End-to-end tests that run the `advent_of_code` binary in a temporary directory.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Output},
+};
+
+use advent_of_code::template::{EXIT_USAGE, EXIT_WRONG_ANSWER, output};
+use tinyjson::JsonValue;
+
+/// A temporary project directory that is removed when dropped.
+struct Project {
+    root: PathBuf,
+}
+
+impl Project {
+    fn new(name: &str) -> Self {
+        let root = env::temp_dir().join(format!("aoc-cli-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for dir in ["src/bin", "data/inputs", "data/examples", "data/puzzles"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        Self { root }
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_advent_of_code"));
+        command
+            .args(args)
+            .arg("--color=never")
+            .env("AOC_ROOT", &self.root);
+        command
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        self.command(args).output().unwrap()
+    }
+
+    /// Runs a command in JSON mode and returns its events.
+    fn run_json(&self, args: &[&str]) -> Vec<HashMap<String, JsonValue>> {
+        let output = self.run(&[args, &["--json"]].concat());
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| output::parse_event(line).expect("expected only JSON events"))
+            .collect()
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    fn write(&self, path: &str, contents: &str) {
+        fs::write(self.path(path), contents).unwrap();
+    }
+
+    fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.path(path)).unwrap()
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn event_names(events: &[HashMap<String, JsonValue>]) -> Vec<&str> {
+    events
+        .iter()
+        .map(|event| event["event"].get::<String>().unwrap().as_str())
+        .collect()
+}
+
+fn exists(path: impl AsRef<Path>) -> bool {
+    path.as_ref().exists()
+}
+
+#[test]
+fn scaffolds_a_day() {
+    let project = Project::new("scaffold");
+
+    let output = project.run(&["scaffold", "3"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let module = project.read("src/bin/03.rs");
+    assert!(module.contains("advent_of_code::solution!(3);"));
+    assert!(!module.contains("%DAY_NUMBER%"));
+    assert_eq!(project.read("data/inputs/03.txt"), "");
+    assert_eq!(project.read("data/examples/03.txt"), "");
+}
+
+#[test]
+fn keeps_existing_files_when_scaffolding_again() {
+    let project = Project::new("rescaffold");
+    project.run(&["scaffold", "3"]);
+    project.write("data/inputs/03.txt", "42");
+
+    let events = project.run_json(&["scaffold", "3"]);
+    assert_eq!(
+        event_names(&events),
+        ["skipped", "skipped", "created", "scaffolded"]
+    );
+    assert_eq!(project.read("data/inputs/03.txt"), "42");
+
+    let events = project.run_json(&["scaffold", "3", "--overwrite"]);
+    assert!(event_names(&events).contains(&"backed_up"));
+    assert_eq!(project.read("data/inputs/03.txt"), "");
+    assert_eq!(project.read("data/inputs/03.txt.bak"), "42");
+}
+
+#[test]
+fn plans_without_writing_on_dry_run() {
+    let project = Project::new("dry-run");
+
+    let events = project.run_json(&["scaffold", "3", "--dry-run"]);
+    assert_eq!(event_names(&events), ["planned", "planned", "planned"]);
+    assert!(!exists(project.path("src/bin/03.rs")));
+    assert!(!exists(project.path("data/inputs/03.txt")));
+}
+
+#[test]
+fn reports_status() {
+    let project = Project::new("status");
+
+    let events = project.run_json(&["status"]);
+    let days = events[0]["days"].get::<Vec<JsonValue>>().unwrap();
+    assert!(days.is_empty());
+
+    project.run(&["scaffold", "7"]);
+    project.write("data/inputs/07.txt", "42");
+
+    let events = project.run_json(&["status"]);
+    let days = events[0]["days"].get::<Vec<JsonValue>>().unwrap();
+    assert_eq!(days.len(), 1);
+
+    let day = days[0].get::<HashMap<String, JsonValue>>().unwrap();
+    assert_eq!(day["day"].get::<String>().unwrap(), "07");
+    assert_eq!(day["bin"], JsonValue::Boolean(true));
+    assert_eq!(day["input"], JsonValue::Boolean(true));
+    assert_eq!(day["example"], JsonValue::Boolean(false));
+    assert_eq!(day["part_two_stubbed"], JsonValue::Boolean(true));
+}
+
+#[test]
+fn unscaffolds_and_archives_days() {
+    let project = Project::new("unscaffold");
+    project.run(&["scaffold", "4"]);
+    project.run(&["scaffold", "5"]);
+
+    let output = project.run(&["unscaffold", "4"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!exists(project.path("src/bin/04.rs")));
+    assert!(!exists(project.path("data/inputs/04.txt")));
+
+    let output = project.run(&["archive", "5"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!exists(project.path("src/bin/05.rs")));
+    assert!(exists(project.path("data/archive/05/src/bin/05.rs")));
+    assert!(exists(project.path("data/archive/05/data/inputs/05.txt")));
+}
+
+#[test]
+fn exits_with_usage_error_for_invalid_arguments() {
+    let project = Project::new("usage");
+
+    let output = project.run(&["frobnicate"]);
+    assert_eq!(output.status.code(), Some(EXIT_USAGE));
+
+    let output = project.run(&["scaffold", "26"]);
+    assert_eq!(output.status.code(), Some(EXIT_USAGE));
+
+    let output = project.run(&["solve", "1", "--submit", "3"]);
+    assert_eq!(output.status.code(), Some(EXIT_USAGE));
+
+    // solutions validate `--submit` as well when they are run directly.
+    project.write("data/inputs/01.txt", "L1");
+    let output = Command::new(env!("CARGO_BIN_EXE_01"))
+        .args(["--submit", "one", "--color=never"])
+        .env("AOC_ROOT", &project.root)
+        .current_dir(&project.root)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(EXIT_USAGE), "{output:?}");
+}
+
+#[test]
+fn prints_errors_as_json_events() {
+    let project = Project::new("errors");
+
+    let output = project.run(&["scaffold", "3", "--template", "missing", "--json"]);
+    assert!(!output.status.success());
+    assert!(!exists(project.path("src/bin/03.rs")));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let event = output::parse_event(stderr.trim()).expect("expected an error event");
+    assert_eq!(event["event"].get::<String>().unwrap(), "error");
+    assert!(
+        event["message"]
+            .get::<String>()
+            .unwrap()
+            .contains("no template named \"missing\"")
+    );
+}
+
+#[test]
+fn prints_warnings_as_json_events() {
+    let project = Project::new("warnings");
+    project.write("data/inputs/01.txt", "L1");
+    project.write(
+        "data/answers.json",
+        r#"{ "data": [{ "day": "01", "part_1": "12345", "part_2": null }] }"#,
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_01"))
+        .args(["--json", "--color=never"])
+        .env("AOC_ROOT", &project.root)
+        .current_dir(&project.root)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(EXIT_WRONG_ANSWER));
+
+    // the heap profiler of the `dhat-heap` feature prints to stderr as well.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let event = stderr
+        .lines()
+        .find_map(output::parse_event)
+        .expect("expected a warning event");
+    assert_eq!(event["event"].get::<String>().unwrap(), "warning");
+    assert!(
+        event["message"]
+            .get::<String>()
+            .unwrap()
+            .contains("differs from the accepted answer 12345")
+    );
+}
+
+#[test]
+fn locates_the_project_root_from_a_subdirectory() {
+    let project = Project::new("subdirectory");
+    project.write("Cargo.toml", "");
+
+    let output = project
+        .command(&["scaffold", "3"])
+        .env_remove("AOC_ROOT")
+        .env_remove("CARGO_MANIFEST_DIR")
+        .current_dir(project.path("data/inputs"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    assert!(exists(project.path("src/bin/03.rs")));
+    assert!(!exists(project.path("data/inputs/src")));
+}
@@ -0,0 +1,157 @@
+//! Regression tests that run every solution against its real input and compare the results with
+//! the answers that were accepted by the advent of code website or recorded with `--record`, as
+//! stored in `data/answers.json`.
+//!
+//! Real inputs are not checked in, so these tests are opt-in:
+//! `cargo test --release --test golden -- --ignored`. Days without an input or without accepted
+//! answers are skipped. Inputs and answers are read from the project root, so `AOC_ROOT` can point
+//! the tests at another directory.
+use std::{env, fs, process::Command};
+
+use advent_of_code::day;
+use advent_of_code::template::workspace::Workspace;
+use advent_of_code::template::{Day, EXIT_UNSOLVED, all_days, output};
+
+/// Paths of the compiled solutions, indexed by day. `None` if the day has not been scaffolded.
+macro_rules! solution_bins {
+    ($($day:literal),+) => {
+        [$( option_env!(concat!("CARGO_BIN_EXE_", $day)) ),+]
+    };
+}
+
+const SOLUTION_BINS: [Option<&str>; 25] = solution_bins!(
+    "01", "02", "03", "04", "05", "06", "07", "08", "09", "10", "11", "12", "13", "14", "15", "16",
+    "17", "18", "19", "20", "21", "22", "23", "24", "25"
+);
+
+/// The outcome of running a solution against its real input.
+enum Golden {
+    Skipped(&'static str),
+    Passed(usize),
+    Failed(String),
+}
+
+/// Runs the solution of a day in `workspace`, which it reads its input and answers from.
+fn check_day(workspace: &Workspace, day: Day, bin: &str) -> Golden {
+    if !workspace.path(format!("data/inputs/{day}.txt")).is_file() {
+        return Golden::Skipped("no input");
+    }
+
+    let output = Command::new(bin)
+        .args(["--json", "--color=never"])
+        .env("AOC_ROOT", workspace.root())
+        .current_dir(workspace.root())
+        .output();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => return Golden::Failed(format!("failed to run {bin}: {e}")),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut checked = 0;
+    let mut failures = vec![];
+
+    for event in stdout.lines().filter_map(output::parse_event) {
+        if event["event"]
+            .get::<String>()
+            .is_none_or(|name| name != "part")
+        {
+            continue;
+        }
+
+        // `expected` is only set if an answer has been accepted.
+        let Some(expected) = event.get("expected").and_then(|e| e.get::<String>()) else {
+            continue;
+        };
+
+        checked += 1;
+        let result = event["result"].get::<String>();
+        if result != Some(expected) {
+            let part = event["part"].get::<f64>().copied().unwrap_or_default();
+            let result = result.map_or("None", String::as_str);
+            failures.push(format!(
+                "part {part} returned {result} instead of {expected}"
+            ));
+        }
+    }
+
+    if !failures.is_empty() {
+        Golden::Failed(failures.join(", "))
+    } else if !output.status.success() && output.status.code() != Some(EXIT_UNSOLVED) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Golden::Failed(format!("exited with {}: {}", output.status, stderr.trim()))
+    } else if checked == 0 {
+        Golden::Skipped("no accepted answers")
+    } else {
+        Golden::Passed(checked)
+    }
+}
+
+#[test]
+#[ignore = "requires real inputs, run with `cargo test --release --test golden -- --ignored`"]
+fn solutions_match_accepted_answers() {
+    let workspace = Workspace::locate();
+    let mut failures = vec![];
+
+    for day in all_days() {
+        let Some(bin) = SOLUTION_BINS[usize::from(day.into_inner()) - 1] else {
+            continue;
+        };
+
+        match check_day(&workspace, day, bin) {
+            Golden::Skipped(reason) => eprintln!("Day {day}: skipped, {reason}."),
+            Golden::Passed(parts) => eprintln!("Day {day}: {parts} part(s) match."),
+            Golden::Failed(message) => {
+                eprintln!("Day {day}: {message}.");
+                failures.push(format!("day {day}: {message}"));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "solutions differ from the accepted answers:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn checks_recorded_answers() {
+    let root = env::temp_dir().join(format!("aoc-golden-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("data/inputs")).unwrap();
+
+    let workspace = Workspace::new(&root);
+    let bin = SOLUTION_BINS[0].expect("expected a solution for day 01");
+    fs::copy(
+        Workspace::locate().path("data/examples/01.txt"),
+        workspace.path("data/inputs/01.txt"),
+    )
+    .unwrap();
+
+    let golden = check_day(&workspace, day!(1), bin);
+    assert!(matches!(golden, Golden::Skipped("no accepted answers")));
+
+    let output = Command::new(bin)
+        .args(["--record", "--color=never"])
+        .env("AOC_ROOT", &root)
+        .current_dir(&root)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(matches!(
+        check_day(&workspace, day!(1), bin),
+        Golden::Passed(2)
+    ));
+
+    // answers can be edited by hand as well.
+    let answers = r#"{ "data": [{ "day": "01", "part_1": "-1", "part_2": null }] }"#;
+    fs::write(workspace.path("data/answers.json"), answers).unwrap();
+    assert!(matches!(
+        check_day(&workspace, day!(1), bin),
+        Golden::Failed(_)
+    ));
+
+    let _ = fs::remove_dir_all(&root);
+}